@@ -17,6 +17,7 @@ pub struct GameState {
     pub score: u32,
     pub combo: u32,
     pub stopped_running: bool,
+    pub launched: bool,
     pub tick: usize,
     pub prev_pos: DVec3,
     pub test_state: PredictionState,
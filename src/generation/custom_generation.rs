@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use rand::{seq::SliceRandom, Rng};
+use valence::{math::IVec3, prelude::*};
+
+use crate::utils::*;
+
+/// The `SingleCustomPreset` struct represents a hand-built parkour segment.
+///
+/// Properties:
+///
+/// * `name`: The `name` property is the name of the preset.
+/// * `blocks`: The `blocks` property is of type `HashMap<BlockPos, BlockState>`. It
+/// represents the blocks of the segment.
+/// * `start_pos`: The `start_pos` property is of type `BlockPos`. It represents the
+/// block the player lands on when entering the segment.
+/// * `end_pos`: The `end_pos` property is of type `BlockPos`. It represents the
+/// block the player jumps off of when leaving the segment.
+#[derive(Clone, Debug)]
+pub struct SingleCustomPreset {
+    pub name: String,
+    pub blocks: HashMap<BlockPos, BlockState>,
+    pub start_pos: BlockPos,
+    pub end_pos: BlockPos,
+}
+
+impl SingleCustomPreset {
+    /// Gets a variant of the preset that is moved so its start position is at
+    /// the origin, flipped on the x axis if `flip` is true, and then rotated
+    /// clockwise `rotations` times.
+    pub fn get_variant(&self, rotations: usize, flip: bool) -> Self {
+        let origin = BlockPos::new(0, 0, 0);
+        let start = self.start_pos;
+
+        let mut blocks = self
+            .blocks
+            .iter()
+            .map(|(pos, block)| {
+                (
+                    BlockPos::new(pos.x - start.x, pos.y - start.y, pos.z - start.z),
+                    *block,
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        let mut end_pos = BlockPos::new(
+            self.end_pos.x - start.x,
+            self.end_pos.y - start.y,
+            self.end_pos.z - start.z,
+        );
+
+        if flip {
+            blocks = flip_block_map_x(&blocks, origin);
+            end_pos = end_pos.flip_x(origin);
+        }
+
+        for _ in 0..rotations {
+            blocks = rotate_block_map_cw(&blocks, origin);
+            end_pos = end_pos.rotate_cw(origin);
+        }
+
+        Self {
+            name: self.name.clone(),
+            blocks,
+            start_pos: origin,
+            end_pos,
+        }
+    }
+
+    /// Gets all rotated and flipped variants of the preset that don't have any
+    /// blocks behind the start position. Those would get in the way of the jump
+    /// into the segment.
+    pub fn get_variants(&self) -> Vec<Self> {
+        let mut variants = Vec::new();
+
+        for rotations in 0..4 {
+            for flip in [false, true] {
+                let variant = self.get_variant(rotations, flip);
+
+                if variant.blocks.keys().all(|pos| pos.z >= 0) && variant.end_pos.z >= 0 {
+                    variants.push(variant);
+                }
+            }
+        }
+
+        variants
+    }
+}
+
+/// The `MultiCustomPreset` struct represents a hand-built parkour segment made out
+/// of multiple pieces. Each piece is a `SingleCustomPreset`, and the start
+/// position of a piece is placed at the end position of the piece before it.
+///
+/// Properties:
+///
+/// * `name`: The `name` property is the name of the preset.
+/// * `start`: The `start` property is of type `SingleCustomPreset`. It represents
+/// the first piece.
+/// * `middles`: The `middles` property is of type `Vec<SingleCustomPreset>`. It
+/// represents the pieces that can be repeated in the middle. A random one is
+/// chosen each time.
+/// * `end`: The `end` property is of type `SingleCustomPreset`. It represents the
+/// last piece.
+/// * `min_middles`: The `min_middles` property is the minimum amount of middle
+/// pieces.
+/// * `max_middles`: The `max_middles` property is the maximum amount of middle
+/// pieces.
+#[derive(Clone, Debug)]
+pub struct MultiCustomPreset {
+    pub name: String,
+    pub start: SingleCustomPreset,
+    pub middles: Vec<SingleCustomPreset>,
+    pub end: SingleCustomPreset,
+    pub min_middles: usize,
+    pub max_middles: usize,
+}
+
+impl MultiCustomPreset {
+    /// Stitches the start piece, a random amount of middle pieces, and the end
+    /// piece together into a single preset.
+    pub fn build(&self) -> SingleCustomPreset {
+        let mut rng = rand::thread_rng();
+
+        let mut pieces = vec![&self.start];
+        for _ in 0..rng.gen_range(self.min_middles..=self.max_middles) {
+            pieces.push(
+                self.middles
+                    .choose(&mut rng)
+                    .unwrap_or_else(|| panic!("No middle pieces in preset `{}`", self.name)),
+            );
+        }
+        pieces.push(&self.end);
+
+        let mut blocks = HashMap::new();
+        let mut offset = BlockPos::new(0, 0, 0);
+
+        for piece in pieces {
+            let piece = piece.get_variant(0, false);
+
+            for (pos, block) in piece.blocks {
+                blocks.insert(pos + offset, block);
+            }
+
+            offset = offset + piece.end_pos;
+        }
+
+        SingleCustomPreset {
+            name: self.name.clone(),
+            blocks,
+            start_pos: BlockPos::new(0, 0, 0),
+            end_pos: offset,
+        }
+    }
+}
+
+/// The `ComplexCustomTile` struct represents a tile of a `ComplexCustomPreset`. A
+/// tile is centered on the origin and goes from `-tile_size / 2` to `tile_size / 2`
+/// on the x and z axes. A face with a socket must have a block in the middle of
+/// that face at y 0, so the player can jump to the next tile.
+///
+/// Properties:
+///
+/// * `name`: The `name` property is the name of the tile.
+/// * `blocks`: The `blocks` property is of type `HashMap<BlockPos, BlockState>`. It
+/// represents the blocks of the tile.
+/// * `sockets`: The `sockets` property is of type `HashMap<BlockPos, String>`. It
+/// maps the direction of a face to the name of its socket. Two tiles can only be
+/// next to each other if the sockets on the touching faces have the same name.
+/// Faces without a socket are closed.
+#[derive(Clone, Debug)]
+pub struct ComplexCustomTile {
+    pub name: String,
+    pub blocks: HashMap<BlockPos, BlockState>,
+    pub sockets: HashMap<BlockPos, String>,
+}
+
+impl ComplexCustomTile {
+    /// Gets the tile rotated clockwise around its center.
+    pub fn rotate_cw(&self) -> Self {
+        let origin = BlockPos::new(0, 0, 0);
+
+        Self {
+            name: self.name.clone(),
+            blocks: rotate_block_map_cw(&self.blocks, origin),
+            sockets: self
+                .sockets
+                .iter()
+                .map(|(dir, socket)| (dir.rotate_cw(origin), socket.clone()))
+                .collect(),
+        }
+    }
+
+    /// Gets all four rotations of the tile.
+    pub fn get_rotations(&self) -> Vec<Self> {
+        let mut rotations = vec![self.clone()];
+
+        for i in 1..4 {
+            rotations.push(rotations[i - 1].rotate_cw());
+        }
+
+        rotations
+    }
+}
+
+/// The `ComplexCustomPreset` struct represents a tile-based parkour segment. The
+/// tiles are laid out on a grid using a randomized DFS, so every tile can be
+/// reached from the entry.
+///
+/// Properties:
+///
+/// * `name`: The `name` property is the name of the preset.
+/// * `tiles`: The `tiles` property is of type `Vec<ComplexCustomTile>`. It represents
+/// the tiles that can be used. They are used in all four rotations, so there should
+/// be a tile for every set of open faces up to rotation.
+/// * `tile_size`: The `tile_size` property is of type `i32`. It represents the width
+/// and length of a tile. It should be odd, so the tile has a middle.
+/// * `size`: The `size` property is of type `IVec3`. It represents the amount of
+/// tiles on the x and z axes. The y axis is not used.
+#[derive(Clone, Debug)]
+pub struct ComplexCustomPreset {
+    pub name: String,
+    pub tiles: Vec<ComplexCustomTile>,
+    pub tile_size: i32,
+    pub size: IVec3,
+}
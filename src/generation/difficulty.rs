@@ -0,0 +1,135 @@
+use std::f32::consts::PI;
+
+use valence::{math::DVec2, prelude::*};
+
+use crate::{
+    prediction::{
+        collision_shapes::{get_block_top, get_collision_shape},
+        prediction_state::PredictionState,
+    },
+    utils::*,
+};
+
+use super::{generation::Generation, generator::GenerationType, validator::get_landing_block};
+
+/// How much each part of the jump adds to the difficulty, at most.
+const MARGIN_WEIGHT: f32 = 3.;
+const YAW_WEIGHT: f32 = 1.;
+const HEIGHT_WEIGHT: f32 = 1.;
+const HITBOX_WEIGHT: f32 = 2.;
+
+/// How many blocks of margin a jump needs to have to not be hard at all.
+const EASY_MARGIN: f64 = 2.;
+
+/// How high a running jump can go, roughly.
+const MAX_JUMP_HEIGHT: f64 = 1.25;
+
+/// Gets how hard it is to get into `generation` with `jump`, and through it. Easy
+/// jumps are close to 0. The difficulty is made up of:
+///
+/// * How much further the jump could have gone than it needs to.
+/// * How much the player has to turn after landing to get through the generation.
+/// * How far up the jump goes.
+/// * How small the top of the landing block is.
+/// * The mechanics of the generation type, like moving blocks or slime.
+pub fn get_difficulty(
+    jump: PredictionState,
+    generation: &Generation,
+    generation_type: &GenerationType,
+) -> f32 {
+    let mut difficulty = get_mechanic_difficulty(generation_type);
+
+    difficulty += get_turn(jump, generation) / PI * YAW_WEIGHT;
+
+    let Some(block) = get_landing_block(generation) else {
+        return difficulty;
+    };
+
+    let top = get_block_top(block);
+    let target_y = generation.offset.y as f64 + top;
+
+    let height = (target_y - jump.pos.y) / MAX_JUMP_HEIGHT;
+    difficulty += height.clamp(0., 1.) as f32 * HEIGHT_WEIGHT;
+
+    // Only the boxes at the top of the block can be landed on.
+    let offset = generation.offset.to_vec3().as_dvec3();
+    let top_boxes = get_collision_shape(block)
+        .into_iter()
+        .filter(|collision_box| collision_box.max.y == top)
+        .collect::<Vec<_>>();
+
+    let area = top_boxes
+        .iter()
+        .map(|collision_box| {
+            let size = collision_box.max - collision_box.min;
+            size.x * size.z
+        })
+        .sum::<f64>();
+    difficulty += (1. - area.min(1.)) as f32 * HITBOX_WEIGHT;
+
+    let mut landed = jump;
+    landed.tick_until_landed(target_y);
+    let from = get_horizontal(jump.pos);
+    let reach = (get_horizontal(landed.pos) - from).length();
+
+    // The player only has to get the edge of their hitbox over the block.
+    let needed = top_boxes
+        .iter()
+        .map(|collision_box| {
+            let min = get_horizontal(offset + collision_box.min) - PLAYER_WIDTH / 2.;
+            let max = get_horizontal(offset + collision_box.max) + PLAYER_WIDTH / 2.;
+            (from.clamp(min, max) - from).length()
+        })
+        .fold(f64::INFINITY, f64::min);
+
+    let margin = (reach - needed) / EASY_MARGIN;
+    difficulty += (1. - margin.clamp(0., 1.)) as f32 * MARGIN_WEIGHT;
+
+    difficulty
+}
+
+/// Gets how much the mechanics of a generation type add to the difficulty.
+fn get_mechanic_difficulty(generation_type: &GenerationType) -> f32 {
+    match generation_type {
+        GenerationType::Single(_) | GenerationType::Island(_) => 0.,
+        GenerationType::Ramp(_)
+        | GenerationType::Bridge(_)
+        | GenerationType::Branch(_)
+        | GenerationType::Door(_) => 0.5,
+        GenerationType::Slime(_)
+        | GenerationType::Indoor(_)
+        | GenerationType::Cave(_)
+        | GenerationType::Climb(_)
+        | GenerationType::Elevator(_)
+        | GenerationType::SingleCustom(_)
+        | GenerationType::MultiCustom(_)
+        | GenerationType::ComplexCustom(_) => 1.,
+        GenerationType::Ice(_) | GenerationType::Slowdown(_) | GenerationType::LaunchPad(_) => 1.5,
+        GenerationType::Snake(_)
+        | GenerationType::BlinkBlocks(_)
+        | GenerationType::HeadHit(_)
+        | GenerationType::Partial(_)
+        | GenerationType::Crumble(_)
+        | GenerationType::Platform(_) => 2.,
+        GenerationType::Neo(_) => 3.,
+    }
+}
+
+/// Gets the angle between the jump into the generation, and the way from its
+/// start to its end. Generations that end where they start don't need a turn.
+fn get_turn(jump: PredictionState, generation: &Generation) -> f32 {
+    let start = get_horizontal(generation.offset.to_vec3().as_dvec3()) + 0.5;
+    let into = start - get_horizontal(jump.pos);
+    let through = get_horizontal(generation.end_state.pos) - start;
+
+    if into.length() < 1. || through.length() < 1. {
+        return 0.;
+    }
+
+    let turn = through.y.atan2(through.x) - into.y.atan2(into.x);
+    ((turn as f32 + PI).rem_euclid(2. * PI) - PI).abs()
+}
+
+fn get_horizontal(pos: DVec3) -> DVec2 {
+    DVec2::new(pos.x, pos.z)
+}
@@ -1,8 +1,30 @@
 use std::collections::{HashMap, HashSet};
 
-use valence::{layer::chunk::IntoBlock, prelude::*};
+use valence::{
+    entity::{
+        block_display::{self, BlockDisplayEntityBundle},
+        display,
+        entity::{Flags, NoGravity, Silent},
+        shulker::ShulkerEntityBundle,
+        EntityId,
+    },
+    layer::chunk::IntoBlock,
+    prelude::*,
+    protocol::{packets::play::EntityPassengersSetS2c, VarInt, WritePacket},
+};
 
-use crate::{prediction::prediction_state::PredictionState, utils::*};
+use crate::{
+    prediction::{
+        collision_shapes::{get_collision_shape, is_standing_on, CollisionBox},
+        prediction_state::PredictionState,
+    },
+    utils::*,
+};
+
+/// How far the player's feet can be from the top of a moving platform while still
+/// standing on it. The client and the server don't agree on exactly where the
+/// platform is, so this is less strict than for blocks.
+const PLATFORM_EPSILON: f64 = 0.1;
 
 /// The `Generation` struct represents a parkour generation.
 ///
@@ -23,20 +45,55 @@ use crate::{prediction::prediction_state::PredictionState, utils::*};
 /// of the parkour generation.
 /// * `end_state`: The `end_state` property is of type `PredictionState`. It represents
 /// the state to expect the player to be in at the end of the parkour generation.
+/// * `snakes`: The `snakes` property is of type `Vec<Snake>`. It represents lines of
+/// blocks that move along a path over time.
+/// * `blink_blocks`: The `blink_blocks` property is of type `Vec<BlinkBlock>`. It
+/// represents blocks that turn on and off over time.
+/// * `fluids`: The `fluids` property is of type `HashMap<BlockPos, BlockState>`. It
+/// represents fluid blocks, like water. They are placed and removed with the
+/// generation, but the player can't reach them by standing on them.
+/// * `launch_pads`: The `launch_pads` property is of type `HashMap<BlockPos, DVec3>`.
+/// It represents blocks that launch the player when they stand on them, with the
+/// velocity they launch the player with. The blocks themselves are in `blocks`.
+/// * `platforms`: The `platforms` property is of type `Vec<MovingPlatform>`. It
+/// represents platforms that move back and forth. They are made of entities
+/// instead of blocks.
+/// * `reached_tick`: The `reached_tick` property is of type `Option<usize>`. It
+/// represents the tick the player reached the generation at, if they have.
+/// * `difficulty`: The `difficulty` property is of type `f32`. It represents how
+/// hard the jump into the generation and the generation itself are, as computed by
+/// `get_difficulty`. Easy generations are close to 0.
 /// * `lines`: The `lines` property is of type `Vec<Line3>`. It represents the path the
 /// player takes through the parkour generation.
 #[derive(Clone, Debug)]
 pub struct Generation {
     pub blocks: HashMap<BlockPos, BlockState>,
     pub children: Vec<ChildGeneration>,
+    pub alt_blocks: HashMap<BlockPos, AltBlock>,
     pub ordered: bool,
     pub offset: BlockPos,
     pub end_state: PredictionState,
+    pub snakes: Vec<Snake>,
+    pub blink_blocks: Vec<BlinkBlock>,
+    pub fluids: HashMap<BlockPos, BlockState>,
+    pub launch_pads: HashMap<BlockPos, DVec3>,
+    pub platforms: Vec<MovingPlatform>,
+    pub reached_tick: Option<usize>,
+    // Not used by the game yet, it's there for scoring and stats.
+    #[allow(dead_code)]
+    pub difficulty: f32,
 }
 
 impl Generation {
-    /// Places the blocks in the generation.
-    pub fn place(&self, world: &mut ChunkLayer) {
+    /// Places the blocks in the generation as they are at the given tick, and
+    /// spawns its entities in `layer`.
+    pub fn place(
+        &mut self,
+        world: &mut ChunkLayer,
+        commands: &mut Commands,
+        layer: Entity,
+        tick: usize,
+    ) {
         for (pos, block) in &self.blocks {
             world.set_block(*pos + self.offset, *block);
         }
@@ -44,13 +101,30 @@ impl Generation {
         for child in &self.children {
             child.place(world, self.offset);
         }
+
+        for (pos, alt_block) in &self.alt_blocks {
+            world.set_block(*pos + self.offset, alt_block.get_block());
+        }
+
+        for snake in &self.snakes {
+            snake.place(world, self.offset, tick);
+        }
+
+        for blink_block in &self.blink_blocks {
+            world.set_block(blink_block.pos + self.offset, blink_block.get_block(tick));
+        }
+
+        for (pos, block) in &self.fluids {
+            world.set_block(*pos + self.offset, *block);
+        }
+
+        for platform in &mut self.platforms {
+            platform.spawn(commands, self.offset, layer, tick);
+        }
     }
 
     /// Removes the blocks in the generation.
-    pub fn remove(
-        &self,
-        world: &mut ChunkLayer,
-    ) {
+    pub fn remove(&self, world: &mut ChunkLayer, commands: &mut Commands) {
         for pos in self.blocks.keys() {
             world.set_block(*pos + self.offset, BlockState::AIR.into_block());
         }
@@ -61,20 +135,145 @@ impl Generation {
                 self.offset,
             );
         }
+
+        for pos in self.alt_blocks.keys() {
+            world.set_block(*pos + self.offset, BlockState::AIR.into_block());
+        }
+
+        for snake in &self.snakes {
+            snake.remove(world, self.offset);
+        }
+
+        for blink_block in &self.blink_blocks {
+            world.set_block(blink_block.pos + self.offset, BlockState::AIR.into_block());
+        }
+
+        for pos in self.fluids.keys() {
+            world.set_block(*pos + self.offset, BlockState::AIR.into_block());
+        }
+
+        self.despawn(commands);
+    }
+
+    /// Despawns the entities in the generation.
+    pub fn despawn(&self, commands: &mut Commands) {
+        for platform in &self.platforms {
+            platform.despawn(commands);
+        }
+    }
+
+    /// Moves the platforms in the generation. If the player is standing on one of
+    /// them, returns how far they should be moved along with it.
+    pub fn move_platforms(
+        &mut self,
+        commands: &mut Commands,
+        writer: &mut impl WritePacket,
+        entity_ids: &Query<Ref<EntityId>>,
+        tick: usize,
+        pos: Position,
+    ) -> DVec3 {
+        let pos = pos.0 - self.offset.to_vec3().as_dvec3();
+        let mut carry = DVec3::ZERO;
+
+        for platform in &mut self.platforms {
+            platform.update(commands, writer, entity_ids, self.offset, tick);
+
+            if tick > 0 && platform.is_standing_on(pos, tick - 1) {
+                carry = platform.get_pos(tick) - platform.get_pos(tick - 1);
+            }
+        }
+
+        carry
+    }
+
+    /// Updates the blocks in the generation that change over time, or when the
+    /// player does something.
+    pub fn update(&mut self, world: &mut ChunkLayer, tick: usize, combo: u32, pos: Position) {
+        let reached_children = self
+            .children
+            .iter()
+            .map(|child| child.reached)
+            .collect::<Vec<_>>();
+        let context = AltBlockContext {
+            reached_children: &reached_children,
+            pos: pos.0,
+            tick,
+            ticks: self
+                .reached_tick
+                .map(|reached_tick| tick.saturating_sub(reached_tick)),
+            combo,
+        };
+
+        for (pos, alt_block) in &mut self.alt_blocks {
+            alt_block.update(world, *pos + self.offset, &context);
+        }
+
+        for child in &mut self.children {
+            child.update(world, self.offset, &context);
+        }
+
+        for snake in &self.snakes {
+            snake.update(world, self.offset, tick);
+        }
+
+        for blink_block in &self.blink_blocks {
+            blink_block.update(world, self.offset, tick);
+        }
     }
 
 
+    /// Gets the velocity to launch the player with, if they are standing on a
+    /// launch pad.
+    pub fn get_launch_velocity(&self, pos: Position) -> Option<DVec3> {
+        let pos = pos.0 - self.offset.to_vec3().as_dvec3();
+
+        get_standing_candidates(pos)
+            .into_iter()
+            .find_map(|block_pos| {
+                let velocity = self.launch_pads.get(&block_pos)?;
+                let block = self.blocks.get(&block_pos)?;
+
+                is_standing_on(pos, block_pos, &get_collision_shape(*block)).then_some(*velocity)
+            })
+    }
+
     /// Returns true if the player has reached any of the blocks.
     pub fn has_reached(&self, pos: Position) -> bool {
-        let poses = get_player_floor_blocks(pos.0 - self.offset.to_vec3().as_dvec3());
+        let pos = pos.0 - self.offset.to_vec3().as_dvec3();
 
-        for pos in poses {
-            if self.blocks.contains_key(&(pos)) {
-                return true;
+        for block_pos in get_standing_candidates(pos) {
+            if let Some(block) = self.blocks.get(&block_pos) {
+                if is_standing_on(pos, block_pos, &get_collision_shape(*block)) {
+                    return true;
+                }
+            }
+
+            if let Some(alt_block) = self.alt_blocks.get(&block_pos) {
+                if is_standing_on(pos, block_pos, &get_collision_shape(alt_block.get_block())) {
+                    return true;
+                }
             }
 
             for child in &self.children {
-                if child.blocks.contains_key(&(pos)) {
+                if child.is_standing_on(pos, block_pos) {
+                    return true;
+                }
+            }
+
+            // Snake blocks are only there when the snake is, so standing on one
+            // of them means the snake is there.
+            for snake in &self.snakes {
+                if snake.blocks.contains(&block_pos)
+                    && is_standing_on(pos, block_pos, &get_collision_shape(snake.block))
+                {
+                    return true;
+                }
+            }
+
+            for blink_block in &self.blink_blocks {
+                if blink_block.pos == block_pos
+                    && is_standing_on(pos, block_pos, &get_collision_shape(blink_block.on_block))
+                {
                     return true;
                 }
             }
@@ -147,6 +346,7 @@ impl Generation {
 #[derive(Clone, Debug)]
 pub struct ChildGeneration {
     pub blocks: HashMap<BlockPos, BlockState>,
+    pub alt_blocks: HashMap<BlockPos, AltBlock>,
     pub check_blocks: HashSet<BlockPos>,
     pub reached: bool,
 }
@@ -157,6 +357,10 @@ impl ChildGeneration {
         for (pos, block) in &self.blocks {
             world.set_block(*pos + offset, *block);
         }
+
+        for (pos, alt_block) in &self.alt_blocks {
+            world.set_block(*pos + offset, alt_block.get_block());
+        }
     }
 
     /// Removes the blocks in the generation.
@@ -168,6 +372,17 @@ impl ChildGeneration {
         for pos in self.blocks.keys() {
             world.set_block(*pos + offset, BlockState::AIR.into_block());
         }
+
+        for pos in self.alt_blocks.keys() {
+            world.set_block(*pos + offset, BlockState::AIR.into_block());
+        }
+    }
+
+    /// Switches the alt blocks whose condition has been met.
+    fn update(&mut self, world: &mut ChunkLayer, offset: BlockPos, context: &AltBlockContext) {
+        for (pos, alt_block) in &mut self.alt_blocks {
+            alt_block.update(world, *pos + offset, context);
+        }
     }
 
     /// Returns true if the player has reached any of the blocks.
@@ -177,10 +392,10 @@ impl ChildGeneration {
             return false;
         }
 
-        let poses = get_player_floor_blocks(pos.0 - offset.to_vec3().as_dvec3());
+        let pos = pos.0 - offset.to_vec3().as_dvec3();
 
-        for pos in poses {
-            if self.blocks.contains_key(&(pos)) || self.check_blocks.contains(&(pos)) {
+        for block_pos in get_standing_candidates(pos) {
+            if self.is_standing_on(pos, block_pos) {
                 self.reached = true;
                 return true;
             }
@@ -188,4 +403,434 @@ impl ChildGeneration {
 
         false
     }
+
+    /// Returns true if the player with their feet at `pos` is standing on the
+    /// block at `block_pos`, if it is part of this child generation. The
+    /// positions are relative to the parent generation.
+    pub fn is_standing_on(&self, pos: DVec3, block_pos: BlockPos) -> bool {
+        if let Some(block) = self.blocks.get(&block_pos) {
+            is_standing_on(pos, block_pos, &get_collision_shape(*block))
+        } else if let Some(alt_block) = self.alt_blocks.get(&block_pos) {
+            is_standing_on(pos, block_pos, &get_collision_shape(alt_block.get_block()))
+        } else if self.check_blocks.contains(&block_pos) {
+            is_standing_on(pos, block_pos, &[CollisionBox::FULL])
+        } else {
+            false
+        }
+    }
+}
+
+/// Gets the positions of the blocks the player could be standing on. Blocks that
+/// are taller than a full block, like fences, stick up into the block above them.
+fn get_standing_candidates(pos: DVec3) -> Vec<BlockPos> {
+    get_player_floor_blocks(pos)
+        .into_iter()
+        .flat_map(|pos| [pos, BlockPos::new(pos.x, pos.y - 1, pos.z)])
+        .collect()
+}
+
+/// The `Snake` struct represents a line of blocks that moves along a path. Every
+/// `delay` ticks, a block is added at the head of the snake and a block is removed
+/// at its tail. Once the snake has moved off the end of the path, it starts over
+/// at the beginning.
+///
+/// Properties:
+///
+/// * `blocks`: The `blocks` property is of type `Vec<BlockPos>`. It represents the
+/// path the snake moves along, in order.
+/// * `block`: The `block` property is of type `BlockState`. It represents the block
+/// the snake is made of.
+/// * `length`: The `length` property is of type `usize`. It represents how many
+/// blocks long the snake is.
+/// * `delay`: The `delay` property is of type `usize`. It represents the amount of
+/// ticks between each move of the snake.
+/// * `phase`: The `phase` property is of type `usize`. It represents how far along
+/// its path the snake is at tick 0.
+#[derive(Clone, Debug)]
+pub struct Snake {
+    pub blocks: Vec<BlockPos>,
+    pub block: BlockState,
+    pub length: usize,
+    pub delay: usize,
+    pub phase: usize,
+}
+
+impl Snake {
+    /// Gets the index of the head of the snake at the given tick. Indices past
+    /// the end of the path mean the head has already moved off of it.
+    fn get_head(&self, tick: usize) -> usize {
+        (tick / self.delay + self.phase) % (self.blocks.len() + self.length)
+    }
+
+    /// Places the blocks the snake is on at the given tick.
+    pub fn place(&self, world: &mut ChunkLayer, offset: BlockPos, tick: usize) {
+        let head = self.get_head(tick);
+
+        for index in (head + 1).saturating_sub(self.length)..=head {
+            if let Some(pos) = self.blocks.get(index) {
+                world.set_block(*pos + offset, self.block);
+            }
+        }
+    }
+
+    /// Moves the snake forward if it's time to do so.
+    pub fn update(&self, world: &mut ChunkLayer, offset: BlockPos, tick: usize) {
+        if tick % self.delay != 0 {
+            return;
+        }
+
+        let head = self.get_head(tick);
+
+        if let Some(pos) = self.blocks.get(head) {
+            world.set_block(*pos + offset, self.block);
+        }
+
+        if head >= self.length {
+            if let Some(pos) = self.blocks.get(head - self.length) {
+                world.set_block(*pos + offset, BlockState::AIR.into_block());
+            }
+        }
+    }
+
+    /// Removes every block the snake could be on.
+    pub fn remove(&self, world: &mut ChunkLayer, offset: BlockPos) {
+        for pos in &self.blocks {
+            world.set_block(*pos + offset, BlockState::AIR.into_block());
+        }
+    }
+}
+
+/// The `BlinkBlock` struct represents a block that is solid for `on_ticks` ticks
+/// and then replaced by `off_block` for `off_ticks` ticks, over and over.
+///
+/// Properties:
+///
+/// * `pos`: The `pos` property is of type `BlockPos`. It represents the position of
+/// the block.
+/// * `on_block`: The `on_block` property is of type `BlockState`. It represents the
+/// block that is placed when the block is on.
+/// * `off_block`: The `off_block` property is of type `BlockState`. It represents
+/// the block that is placed when the block is off. This is usually air.
+/// * `on_ticks`: The `on_ticks` property is of type `usize`. It represents how many
+/// ticks the block stays on.
+/// * `off_ticks`: The `off_ticks` property is of type `usize`. It represents how
+/// many ticks the block stays off.
+/// * `phase`: The `phase` property is of type `usize`. It represents how many ticks
+/// into its cycle the block is at tick 0.
+#[derive(Clone, Debug)]
+pub struct BlinkBlock {
+    pub pos: BlockPos,
+    pub on_block: BlockState,
+    pub off_block: BlockState,
+    pub on_ticks: usize,
+    pub off_ticks: usize,
+    pub phase: usize,
+}
+
+impl BlinkBlock {
+    /// Returns true if the block is on at the given tick.
+    pub fn is_on(&self, tick: usize) -> bool {
+        (tick + self.phase) % (self.on_ticks + self.off_ticks) < self.on_ticks
+    }
+
+    /// Gets the block that is placed at the given tick.
+    pub fn get_block(&self, tick: usize) -> BlockState {
+        if self.is_on(tick) {
+            self.on_block
+        } else {
+            self.off_block
+        }
+    }
+
+    /// Turns the block on or off if it's time to do so.
+    pub fn update(&self, world: &mut ChunkLayer, offset: BlockPos, tick: usize) {
+        if tick > 0 && self.is_on(tick) == self.is_on(tick - 1) {
+            return;
+        }
+
+        world.set_block(self.pos + offset, self.get_block(tick));
+    }
+}
+
+/// The `AltBlock` struct represents a block that switches to another block once
+/// its condition is met, like a door that opens or a bridge that appears. It
+/// only switches once.
+///
+/// Properties:
+///
+/// * `block`: The `block` property is of type `BlockState`. It represents the
+/// block that is placed at first. This can be air.
+/// * `alt_block`: The `alt_block` property is of type `BlockState`. It represents
+/// the block that is placed once the condition is met.
+/// * `condition`: The `condition` property is of type `AltBlockCondition`. It
+/// represents when the block switches.
+/// * `switched`: The `switched` property is of type `bool`. It represents whether
+/// or not the block has switched to `alt_block`.
+/// * `stepped_on_tick`: The `stepped_on_tick` property is of type `Option<usize>`.
+/// It represents the tick the player first stood on the block at, if they have.
+#[derive(Clone, Debug)]
+pub struct AltBlock {
+    pub block: BlockState,
+    pub alt_block: BlockState,
+    pub condition: AltBlockCondition,
+    pub switched: bool,
+    pub stepped_on_tick: Option<usize>,
+}
+
+impl AltBlock {
+    pub fn new(block: BlockState, alt_block: BlockState, condition: AltBlockCondition) -> Self {
+        Self {
+            block,
+            alt_block,
+            condition,
+            switched: false,
+            stepped_on_tick: None,
+        }
+    }
+
+    /// Gets the block that is currently placed.
+    pub fn get_block(&self) -> BlockState {
+        if self.switched {
+            self.alt_block
+        } else {
+            self.block
+        }
+    }
+
+    /// Switches the block if its condition has been met.
+    fn update(&mut self, world: &mut ChunkLayer, pos: BlockPos, context: &AltBlockContext) {
+        if self.switched {
+            return;
+        }
+
+        if self.stepped_on_tick.is_none()
+            && is_standing_on(context.pos, pos, &get_collision_shape(self.get_block()))
+        {
+            self.stepped_on_tick = Some(context.tick);
+        }
+
+        if !self.condition.is_met(context, self.stepped_on_tick) {
+            return;
+        }
+
+        self.switched = true;
+        world.set_block(pos, self.alt_block);
+    }
+}
+
+/// The `AltBlockCondition` enum represents the condition for an `AltBlock` to
+/// switch.
+///
+/// Variants:
+///
+/// * `ChildReached`: The `ChildReached` variant is met once the player has reached
+/// the child generation with the given index in the generation.
+/// * `Ticks`: The `Ticks` variant is met the given amount of ticks after the player
+/// reached the generation.
+/// * `Combo`: The `Combo` variant is met once the combo of the player is at least
+/// the given amount.
+/// * `SteppedOn`: The `SteppedOn` variant is met the given amount of ticks after
+/// the player first stood on the block.
+#[derive(Clone, Debug)]
+pub enum AltBlockCondition {
+    ChildReached(usize),
+    Ticks(usize),
+    Combo(u32),
+    SteppedOn(usize),
+}
+
+impl AltBlockCondition {
+    fn is_met(&self, context: &AltBlockContext, stepped_on_tick: Option<usize>) -> bool {
+        match self {
+            AltBlockCondition::ChildReached(index) => context
+                .reached_children
+                .get(*index)
+                .copied()
+                .unwrap_or(false),
+            AltBlockCondition::Ticks(ticks) => context.ticks.map_or(false, |t| t >= *ticks),
+            AltBlockCondition::Combo(combo) => context.combo >= *combo,
+            AltBlockCondition::SteppedOn(ticks) => {
+                stepped_on_tick.map_or(false, |t| context.tick - t >= *ticks)
+            }
+        }
+    }
+}
+
+/// What alt block conditions are checked against.
+struct AltBlockContext<'a> {
+    reached_children: &'a [bool],
+    pos: DVec3,
+    tick: usize,
+    ticks: Option<usize>,
+    combo: u32,
+}
+
+/// The `MovingPlatform` struct represents a block that slides back and forth
+/// between two points. It is made of a block display that shows the block, with
+/// an invisible shulker riding it, which the player can stand on. The client snaps
+/// shulkers to the block grid, unless they are riding something.
+///
+/// Properties:
+///
+/// * `from`: The `from` property is of type `DVec3`. It represents the position
+/// the platform starts at.
+/// * `to`: The `to` property is of type `DVec3`. It represents the position the
+/// platform moves to before it turns around.
+/// * `block`: The `block` property is of type `BlockState`. It represents the block
+/// the platform looks like.
+/// * `ticks`: The `ticks` property is of type `usize`. It represents the amount of
+/// ticks it takes the platform to move from `from` to `to`.
+/// * `phase`: The `phase` property is of type `usize`. It represents how many ticks
+/// into its cycle the platform is at tick 0.
+/// * `display`: The `display` property is of type `Option<Entity>`. It represents
+/// the block display of the platform, once it is spawned.
+/// * `shulker`: The `shulker` property is of type `Option<Entity>`. It represents
+/// the shulker of the platform, once it is spawned.
+/// * `mounted`: The `mounted` property is of type `bool`. It represents whether or
+/// not the client has been told that the shulker is riding the block display.
+#[derive(Clone, Debug)]
+pub struct MovingPlatform {
+    pub from: DVec3,
+    pub to: DVec3,
+    pub block: BlockState,
+    pub ticks: usize,
+    pub phase: usize,
+    pub display: Option<Entity>,
+    pub shulker: Option<Entity>,
+    pub mounted: bool,
+}
+
+impl MovingPlatform {
+    /// # Panics
+    ///
+    /// Panics if `ticks` is 0.
+    pub fn new(from: DVec3, to: DVec3, block: BlockState, ticks: usize, phase: usize) -> Self {
+        assert!(ticks > 0, "A moving platform can't move in 0 ticks");
+
+        Self {
+            from,
+            to,
+            block,
+            ticks,
+            phase,
+            display: None,
+            shulker: None,
+            mounted: false,
+        }
+    }
+
+    /// Gets the position of the lowest corner of the platform at the given tick.
+    pub fn get_pos(&self, tick: usize) -> DVec3 {
+        let cycle = (tick + self.phase) % (self.ticks * 2);
+        let progress = if cycle < self.ticks {
+            cycle
+        } else {
+            self.ticks * 2 - cycle
+        };
+
+        self.from.lerp(self.to, progress as f64 / self.ticks as f64)
+    }
+
+    /// Returns true if the player with their feet at `pos` is standing on top of
+    /// the platform at the given tick.
+    pub fn is_standing_on(&self, pos: DVec3, tick: usize) -> bool {
+        let min = self.get_pos(tick);
+        let max = min + DVec3::ONE;
+
+        (pos.y - max.y).abs() < PLATFORM_EPSILON
+            && pos.x + PLAYER_WIDTH / 2. > min.x
+            && pos.x - PLAYER_WIDTH / 2. < max.x
+            && pos.z + PLAYER_WIDTH / 2. > min.z
+            && pos.z - PLAYER_WIDTH / 2. < max.z
+    }
+
+    /// Spawns the entities of the platform in `layer`, where it is at the given
+    /// tick. Both entities are centered on the platform, since that's where a
+    /// shulker's hitbox is, and the block of the display is moved back to the
+    /// corner.
+    pub fn spawn(&mut self, commands: &mut Commands, offset: BlockPos, layer: Entity, tick: usize) {
+        let pos = self.get_center(offset, tick);
+
+        let display = commands
+            .spawn(BlockDisplayEntityBundle {
+                layer: EntityLayerId(layer),
+                position: Position(pos),
+                block_display_block_state: block_display::BlockState(self.block),
+                display_translation: display::Translation(Vec3::new(-0.5, 0., -0.5)),
+                ..Default::default()
+            })
+            .id();
+
+        let mut flags = Flags::default();
+        flags.set_invisible(true);
+        let shulker = commands
+            .spawn(ShulkerEntityBundle {
+                layer: EntityLayerId(layer),
+                position: Position(pos),
+                entity_flags: flags,
+                entity_no_gravity: NoGravity(true),
+                entity_silent: Silent(true),
+                ..Default::default()
+            })
+            .id();
+
+        self.display = Some(display);
+        self.shulker = Some(shulker);
+        self.mounted = false;
+    }
+
+    /// Despawns the entities of the platform.
+    pub fn despawn(&self, commands: &mut Commands) {
+        for entity in self.display.iter().chain(&self.shulker) {
+            commands.entity(*entity).insert(Despawned);
+        }
+    }
+
+    /// Moves the block display of the platform to where it is at the given tick.
+    /// The shulker rides along with it.
+    ///
+    /// The client has to know about both entities before the shulker can be put
+    /// on the block display, so that only happens once they have been around
+    /// since the last update.
+    pub fn update(
+        &mut self,
+        commands: &mut Commands,
+        writer: &mut impl WritePacket,
+        entity_ids: &Query<Ref<EntityId>>,
+        offset: BlockPos,
+        tick: usize,
+    ) {
+        let (Some(display), Some(shulker)) = (self.display, self.shulker) else {
+            return;
+        };
+
+        commands
+            .entity(display)
+            .insert(Position(self.get_center(offset, tick)));
+
+        if self.mounted {
+            return;
+        }
+
+        let (Ok(display_id), Ok(shulker_id)) = (entity_ids.get(display), entity_ids.get(shulker))
+        else {
+            return;
+        };
+
+        if display_id.is_added() || shulker_id.is_added() {
+            return;
+        }
+
+        writer.write_packet(&EntityPassengersSetS2c {
+            entity_id: VarInt(display_id.get()),
+            passengers: vec![VarInt(shulker_id.get())],
+        });
+        self.mounted = true;
+    }
+
+    /// Gets the position of the center of the bottom of the platform at the
+    /// given tick, relative to the world.
+    fn get_center(&self, offset: BlockPos, tick: usize) -> DVec3 {
+        self.get_pos(tick) + offset.to_vec3().as_dvec3() + DVec3::new(0.5, 0., 0.5)
+    }
 }
@@ -2,7 +2,22 @@ use std::collections::HashMap;
 
 use crate::{prediction::prediction_state::PredictionState, utils::*};
 
-use super::{block_collection::*, generation::*, theme::GenerationTheme};
+use super::{
+    block_collection::*,
+    custom_generation::{ComplexCustomPreset, MultiCustomPreset, SingleCustomPreset},
+    difficulty::get_difficulty,
+    generation::*,
+    generators::{
+        blink_blocks::BlinkBlocksGenParams, branch::BranchGenParams, bridge::BridgeGenParams,
+        cave::CaveGenParams, climb::ClimbGenParams, crumble::CrumbleGenParams, door::DoorGenParams,
+        elevator::ElevatorGenParams, head_hit::HeadHitGenParams, ice::IceGenParams,
+        indoor::IndoorGenParams, island::IslandGenParams, launch_pad::LaunchPadGenParams,
+        neo::NeoGenParams, partial::PartialGenParams, platform::PlatformGenParams,
+        slime::SlimeGenParams, slowdown::SlowdownGenParams, snake::SnakeGenParams,
+    },
+    theme::GenerationTheme,
+    validator::{validate, Rejection},
+};
 use valence::prelude::*;
 
 /// The `GenerationType` enum represents the different types of parkour generations
@@ -10,31 +25,133 @@ use valence::prelude::*;
 ///
 /// Variants:
 /// * `Single`: The `Single` variant represents a single block.
-/// * `Slime`: The `Slime` variant represents a slime block. // TODO
+/// * `Slime`: The `Slime` variant represents a slime block. The player drops onto
+/// it and bounces to a platform that is higher or farther away.
 /// * `Ramp`: The `Ramp` variant represents blocks and slabs that are used to create
-/// a ramp.
+/// a ramp. It uses the `"<name>_full"`, `"<name>_slab"` and `"<name>_stair"` blocks.
 /// * `Island`: The `Island` variant represents blocks that are used to create an
-/// island.
+/// island. The shape of the island is generated using noise.
 /// * `Indoor`: The `Indoor` variant represents blocks that are used to create an
-/// indoor area.
+/// indoor area. It has walls, a ceiling, and platforms to jump between.
 /// * `Cave`: The `Cave` variant represents blocks that are used to create a cave.
+/// The cave is carved out using 3D noise.
 /// * `Snake`: The `Snake` variant represents blocks that are used to create a
-/// snake.
+/// snake. The snake is a line of blocks that moves forward over time.
 /// * `BlinkBlocks`: The `BlinkBlocks` variant represents blocks that are used to
-/// create a blinking platform.
+/// create a blinking platform. The platforms turn on and off on a fixed schedule.
+/// * `HeadHit`: The `HeadHit` variant represents blocks that are used to create
+/// head hit jumps. Every takeoff spot has a ceiling block over it.
+/// * `Neo`: The `Neo` variant represents blocks that are used to create a neo jump.
+/// A wall is in the way, so the player has to curve around it mid-air.
+/// * `Climb`: The `Climb` variant represents blocks that are used to create a
+/// climb. The player climbs a ladder or vine on a column to reach the next jump.
+/// * `Ice`: The `Ice` variant represents blocks that are used to create an ice
+/// runway. The player builds up speed on it to make a longer jump.
+/// * `Slowdown`: The `Slowdown` variant represents blocks that are used to create
+/// a surface that slows the player down, like soul sand, honey or cobwebs.
+/// * `Elevator`: The `Elevator` variant represents blocks that are used to create
+/// a water or bubble column that carries the player up or down.
+/// * `Partial`: The `Partial` variant represents blocks that are used to create
+/// jumps onto blocks that are not full cubes, like trapdoors, fences and heads.
+/// * `Branch`: The `Branch` variant represents blocks that are used to create a
+/// few routes next to each other. Harder routes are worth more points.
+/// * `Bridge`: The `Bridge` variant represents blocks that are used to create a
+/// bridge that appears over a gap that is too long to jump over.
+/// * `Crumble`: The `Crumble` variant represents blocks that are used to create
+/// jumps onto blocks that crumble shortly after the player stands on them.
+/// * `Door`: The `Door` variant represents blocks that are used to create a wall
+/// with doors in it. One opens once the player stands on a button, the other one
+/// once their combo is high enough.
+/// * `Platform`: The `Platform` variant represents blocks that are used to create
+/// a platform that moves back and forth over a gap.
+/// * `LaunchPad`: The `LaunchPad` variant represents blocks that are used to
+/// create a launch pad that throws the player into a long jump.
 /// * `SingleCustom`: The `SingleCustom` variant represents a custom parkour
-/// generation. It has preset blocks, a start position, and an end position.
+/// generation. It has preset blocks, a start position, and an end position. It is
+/// randomly rotated and flipped.
 /// * `MultiCustom`: The `MultiCustom` variant represents a custom parkour
 /// generation. It has a start custom generation, a number of middle custom
-/// generations, and an end custom generation.
+/// generations, and an end custom generation. The number of middle generations
+/// is random, so the length changes every time.
 /// * `ComplexCustom`: The `ComplexCustom` variant represents a custom parkour
 /// generation that is generated using a DFS algorithm. It produces a tile-based
 /// generation.
 #[derive(Clone, Debug)]
 pub enum GenerationType {
     Single(String),
+    Slime(SlimeGenParams),
+    Ramp(String),
+    Island(IslandGenParams),
+    Indoor(IndoorGenParams),
+    Cave(CaveGenParams),
+    Snake(SnakeGenParams),
+    BlinkBlocks(BlinkBlocksGenParams),
+    HeadHit(HeadHitGenParams),
+    Neo(NeoGenParams),
+    Climb(ClimbGenParams),
+    Ice(IceGenParams),
+    Slowdown(SlowdownGenParams),
+    Elevator(ElevatorGenParams),
+    Partial(PartialGenParams),
+    Branch(BranchGenParams),
+    Bridge(BridgeGenParams),
+    Crumble(CrumbleGenParams),
+    Door(DoorGenParams),
+    Platform(PlatformGenParams),
+    LaunchPad(LaunchPadGenParams),
+    SingleCustom(SingleCustomPreset),
+    MultiCustom(MultiCustomPreset),
+    ComplexCustom(ComplexCustomPreset),
 }
 
+impl GenerationType {
+    /// Gets the name of the generation type. Custom generations use the name of
+    /// their preset.
+    pub fn get_name(&self) -> &str {
+        match self {
+            GenerationType::Single(_) => "Single",
+            GenerationType::Slime(_) => "Slime",
+            GenerationType::Ramp(_) => "Ramp",
+            GenerationType::Island(_) => "Island",
+            GenerationType::Indoor(_) => "Indoor",
+            GenerationType::Cave(_) => "Cave",
+            GenerationType::Snake(_) => "Snake",
+            GenerationType::BlinkBlocks(_) => "BlinkBlocks",
+            GenerationType::HeadHit(_) => "HeadHit",
+            GenerationType::Neo(_) => "Neo",
+            GenerationType::Climb(_) => "Climb",
+            GenerationType::Ice(_) => "Ice",
+            GenerationType::Slowdown(_) => "Slowdown",
+            GenerationType::Elevator(_) => "Elevator",
+            GenerationType::Partial(_) => "Partial",
+            GenerationType::Branch(_) => "Branch",
+            GenerationType::Bridge(_) => "Bridge",
+            GenerationType::Crumble(_) => "Crumble",
+            GenerationType::Door(_) => "Door",
+            GenerationType::Platform(_) => "Platform",
+            GenerationType::LaunchPad(_) => "LaunchPad",
+            GenerationType::SingleCustom(preset) => &preset.name,
+            GenerationType::MultiCustom(preset) => &preset.name,
+            GenerationType::ComplexCustom(preset) => &preset.name,
+        }
+    }
+
+    /// Returns true if the generation type can fail to generate, depending on
+    /// where it is generated. These are the generation types `generate` returns
+    /// `None` for.
+    pub fn can_fail(&self) -> bool {
+        matches!(
+            self,
+            GenerationType::Neo(_) | GenerationType::Slowdown(_) | GenerationType::Partial(_)
+        )
+    }
+}
+
+/// How many times a generation is regenerated with a random generation type after
+/// being rejected by the validator, before falling back to the first generation
+/// type of the theme. The fallback gets as many tries.
+const MAX_GENERATION_ATTEMPTS: usize = 10;
+
 /// The `Generator` struct represents a parkour generator.
 ///
 /// Properties:
@@ -63,7 +180,9 @@ impl Generator {
 
         let yaw = random_yaw();
 
-        let mut g = s.generate(JumpDirection::DoesntMatter); // no lines for first generation
+        let mut g = s
+            .generate(JumpDirection::DoesntMatter) // no lines for first generation
+            .expect("The first generation type of a theme can't fail");
 
         g.offset = start;
         g.end_state = PredictionState::running_jump_block(start, yaw);
@@ -77,33 +196,88 @@ impl Generator {
         generation: &Generation,
     ) -> Generation {
         let theme = theme.clone();
-        let mut state = generation.end_state;
+        let mut jump = generation.end_state;
+        let mut rejections = Vec::new();
 
-        let target_y = (state.pos.y as i32 + direction.get_y_offset()) as f64;
+        let target_y = (jump.pos.y as i32 + direction.get_y_offset()) as f64;
 
-        let g = loop {
-            let mut new_state = state;
-            new_state.tick();
-
-            if new_state.vel.y > 0. || new_state.pos.y > target_y {
-                state = new_state;
+        // Once every random generation type has been rejected, the first
+        // generation type of the theme is tried instead, which is a single block.
+        for attempt in 0..MAX_GENERATION_ATTEMPTS * 2 {
+            let generation_type = if attempt < MAX_GENERATION_ATTEMPTS {
+                theme.get_random_generation_type()
             } else {
-                break Self {
-                    generation_type: theme.get_random_generation_type(),
-                    theme,
-                    start: state.get_block_pos(),
-                };
+                theme.generation_types[0].clone()
+            };
+
+            let mut state = jump;
+            state.tick_until_landed(target_y);
+
+            let g = Self {
+                generation_type,
+                theme: theme.clone(),
+                start: state.get_block_pos(),
+            };
+
+            let Some(mut next) = g.generate(direction) else {
+                rejections.push((g.generation_type.get_name().to_string(), Rejection::Failed));
+                continue;
+            };
+
+            match validate(generation, jump, &next) {
+                Ok(()) => {
+                    next.difficulty = get_difficulty(jump, &next, &g.generation_type);
+                    return next;
+                }
+                Err(rejection) => {
+                    // The start can't be reached, so the jump is aimed somewhere
+                    // else instead.
+                    if !rejection.can_retry() {
+                        jump.turn(random_yaw());
+                    }
+
+                    rejections.push((g.generation_type.get_name().to_string(), rejection));
+                }
             }
+        }
+
+        // Nothing could be validated, so the last jump is used anyway. The
+        // rejections are logged to find out why.
+        let mut state = jump;
+        state.tick_until_landed(target_y);
+        let start = state.get_block_pos();
+
+        eprintln!("Every generation at {start:?} was rejected, falling back anyway:");
+        for (name, rejection) in &rejections {
+            eprintln!("  {name}: {rejection}");
+        }
+
+        let g = Self {
+            generation_type: theme.generation_types[0].clone(),
+            theme,
+            start,
         };
 
-        g.generate(direction)
+        let mut next = g
+            .generate(direction)
+            .expect("The first generation type of a theme can't fail");
+        next.difficulty = get_difficulty(jump, &next, &g.generation_type);
+        next
     }
 
-    pub fn generate(&self, direction: JumpDirection) -> Generation {
+    /// Generates a generation of the generation type at `start`. Returns `None`
+    /// if the generation type can't be generated there.
+    pub fn generate(&self, direction: JumpDirection) -> Option<Generation> {
         let mut blocks = HashMap::new();
         let offset: BlockPos = self.start;
-        let children = Vec::new();
-        let ordered = true;
+        let mut children = Vec::new();
+        let mut alt_blocks = HashMap::new();
+        let mut ordered = true;
+        let mut snakes = Vec::new();
+        let mut blink_blocks = Vec::new();
+        let mut fluids = HashMap::new();
+        let mut launch_pads = HashMap::new();
+        let mut platforms = Vec::new();
 
         let params = BlockGenParams {
             direction,
@@ -125,15 +299,74 @@ impl Generator {
                 
                 PredictionState::running_jump_block(self.start, random_yaw())
             }
+            GenerationType::Slime(slime) => self.generate_slime(slime, &params, &mut blocks),
+            GenerationType::Ramp(key) => self.generate_ramp(key, &params, &mut blocks),
+            GenerationType::Island(island) => self.generate_island(island, &params, &mut blocks),
+            GenerationType::Indoor(indoor) => self.generate_indoor(indoor, &params, &mut blocks),
+            GenerationType::Cave(cave) => self.generate_cave(cave, &params, &mut blocks),
+            GenerationType::Snake(snake) => {
+                self.generate_snake(snake, &params, &mut blocks, &mut snakes)
+            }
+            GenerationType::BlinkBlocks(blink) => {
+                self.generate_blink_blocks(blink, &params, &mut blocks, &mut blink_blocks)
+            }
+            GenerationType::HeadHit(head_hit) => {
+                self.generate_head_hit(head_hit, &params, &mut blocks)
+            }
+            GenerationType::Neo(neo) => self.generate_neo(neo, &params, &mut blocks)?,
+            GenerationType::Climb(climb) => self.generate_climb(climb, &params, &mut blocks),
+            GenerationType::Ice(ice) => self.generate_ice(ice, &params, &mut blocks),
+            GenerationType::Slowdown(slowdown) => {
+                self.generate_slowdown(slowdown, &params, &mut blocks)?
+            }
+            GenerationType::Elevator(elevator) => {
+                self.generate_elevator(elevator, &params, &mut blocks, &mut fluids)
+            }
+            GenerationType::Partial(partial) => {
+                self.generate_partial(partial, &params, &mut blocks)?
+            }
+            GenerationType::Branch(branch) => {
+                self.generate_branch(branch, &params, &mut blocks, &mut children, &mut ordered)
+            }
+            GenerationType::Bridge(bridge) => {
+                self.generate_bridge(bridge, &params, &mut blocks, &mut alt_blocks)
+            }
+            GenerationType::Crumble(crumble) => {
+                self.generate_crumble(crumble, &params, &mut blocks, &mut alt_blocks)
+            }
+            GenerationType::Door(door) => {
+                self.generate_door(door, &params, &mut blocks, &mut alt_blocks, &mut children)
+            }
+            GenerationType::Platform(platform) => {
+                self.generate_platform(platform, &params, &mut blocks, &mut platforms)
+            }
+            GenerationType::LaunchPad(launch_pad) => {
+                self.generate_launch_pad(launch_pad, &params, &mut blocks, &mut launch_pads)
+            }
+            GenerationType::SingleCustom(preset) => {
+                self.generate_single_custom(preset, &mut blocks)
+            }
+            GenerationType::MultiCustom(preset) => self.generate_multi_custom(preset, &mut blocks),
+            GenerationType::ComplexCustom(preset) => {
+                self.generate_complex_custom(preset, &mut blocks)
+            }
         };
 
-        Generation {
+        Some(Generation {
             blocks,
             children,
+            alt_blocks,
             ordered,
             offset,
             end_state,
-        }
+            snakes,
+            blink_blocks,
+            fluids,
+            launch_pads,
+            platforms,
+            reached_tick: None,
+            difficulty: 0.,
+        })
     }
 }
 
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use valence::prelude::*;
+
+use crate::{
+    generation::{
+        generation::BlinkBlock,
+        generator::{BlockGenParams, Generator},
+    },
+    prediction::prediction_state::PredictionState,
+    utils::*,
+};
+
+/// The `BlinkBlocksGenParams` struct represents the parameters used to generate
+/// blinking platforms.
+///
+/// Properties:
+///
+/// * `block`: The `block` property is the name of the block used for the
+/// platforms when they are on.
+/// * `ghost`: The `ghost` property is the name of the block used for the
+/// platforms when they are off. If it is `None`, air is used.
+/// * `on_ticks`: The `on_ticks` property is how many ticks the platforms stay
+/// on.
+/// * `off_ticks`: The `off_ticks` property is how many ticks the platforms stay
+/// off.
+#[derive(Clone, Debug)]
+pub struct BlinkBlocksGenParams {
+    pub block: String,
+    pub ghost: Option<String>,
+    pub on_ticks: usize,
+    pub off_ticks: usize,
+}
+
+impl Generator {
+    /// Generates platforms that turn on and off over time, with a solid platform
+    /// on either end.
+    ///
+    /// The platforms are scheduled so that a player who jumps off a platform
+    /// while it's on lands on the next one while that one is on too.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the platforms are never on.
+    pub fn generate_blink_blocks(
+        &self,
+        blink: &BlinkBlocksGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+        blink_blocks: &mut Vec<BlinkBlock>,
+    ) -> PredictionState {
+        assert!(blink.on_ticks > 0, "Blink blocks have to be on for at least 1 tick");
+
+        let mut rng = rand::thread_rng();
+
+        let period = blink.on_ticks + blink.off_ticks;
+        let off_block = match &blink.ghost {
+            Some(ghost) => params.block_map.get_block(ghost),
+            None => BlockState::AIR,
+        };
+
+        let platforms = rng.gen_range(2..=4);
+
+        let mut takeoff = BlockPos::new(0, 0, 0);
+        let mut phase = rng.gen_range(0..period);
+
+        blocks.insert(takeoff, params.block_map.get_block(&blink.block));
+
+        for i in 0..=platforms {
+            let mut state = PredictionState::running_jump_block(takeoff, random_yaw_dist(30.));
+            let flight_ticks = state.tick_until_landed(takeoff.y as f64 + 1.) + 1;
+
+            takeoff = state.get_block_pos();
+
+            // The last platform is always solid.
+            if i == platforms {
+                blocks.insert(takeoff, params.block_map.get_block(&blink.block));
+                break;
+            }
+
+            // The first platform can be on at any time, as the player can wait
+            // on the solid platform before it.
+            if i > 0 {
+                phase = (phase + period - flight_ticks % period) % period;
+            }
+
+            blink_blocks.push(BlinkBlock {
+                pos: takeoff,
+                on_block: params.block_map.get_block(&blink.block),
+                off_block,
+                on_ticks: blink.on_ticks,
+                off_ticks: blink.off_ticks,
+                phase,
+            });
+        }
+
+        PredictionState::running_jump_block(self.start + takeoff, random_yaw())
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::{seq::SliceRandom, Rng};
+use valence::prelude::*;
+
+use crate::{
+    generation::{
+        generation::ChildGeneration,
+        generator::{BlockGenParams, Generator},
+    },
+    prediction::prediction_state::PredictionState,
+    utils::*,
+};
+
+/// The distance between two blocks in a branch, and between the last block of a
+/// branch and the exit.
+const BRANCH_STEP: i32 = 3;
+
+/// The distance between two branches.
+const BRANCH_SPACING: i32 = 3;
+
+/// The heights of the blocks in each kind of branch, and the amount of points the
+/// player gets for taking it. Every branch has the same length, so they all end
+/// at the exit.
+const BRANCHES: [(&[i32], usize); 3] = [(&[0, 0, 0], 1), (&[1, 2, 1], 2), (&[1, 2, 3], 3)];
+
+/// The `BranchGenParams` struct represents the parameters used to generate
+/// branching routes.
+///
+/// Properties:
+///
+/// * `block`: The `block` property is the name of the block the branches are made
+/// of.
+/// * `min_branches`: The `min_branches` property is the minimum amount of
+/// branches.
+/// * `max_branches`: The `max_branches` property is the maximum amount of
+/// branches. There are only 3 kinds of branches, so any more than that are not
+/// generated.
+#[derive(Clone, Debug)]
+pub struct BranchGenParams {
+    pub block: String,
+    pub min_branches: usize,
+    pub max_branches: usize,
+}
+
+impl Generator {
+    /// Generates a start and an exit that are connected by a few branches next to
+    /// each other. The player can take any of them.
+    ///
+    /// Every branch is made up of child generations, and the generation is not
+    /// ordered, so the player gets a point for every child they reach. Harder
+    /// branches are split into more children, so they are worth more points.
+    pub fn generate_branch(
+        &self,
+        branch: &BranchGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+        children: &mut Vec<ChildGeneration>,
+        ordered: &mut bool,
+    ) -> PredictionState {
+        let mut rng = rand::thread_rng();
+
+        let count = rng
+            .gen_range(branch.min_branches..=branch.max_branches)
+            .clamp(1, BRANCHES.len());
+        let mut kinds = BRANCHES.to_vec();
+        kinds.shuffle(&mut rng);
+        kinds.truncate(count);
+
+        let min_x = -BRANCH_SPACING * (count as i32 - 1) / 2;
+        let max_x = min_x + BRANCH_SPACING * (count as i32 - 1);
+        let exit_z = BRANCH_STEP * (BRANCHES[0].0.len() as i32 + 1);
+
+        for x in min_x..=max_x {
+            blocks.insert(
+                BlockPos::new(x, 0, 0),
+                params.block_map.get_block(&branch.block),
+            );
+            blocks.insert(
+                BlockPos::new(x, 0, exit_z),
+                params.block_map.get_block(&branch.block),
+            );
+        }
+
+        for (i, (heights, points)) in kinds.into_iter().enumerate() {
+            let x = min_x + BRANCH_SPACING * i as i32;
+
+            let mut branch_children = vec![HashMap::new(); points];
+            for (j, y) in heights.iter().enumerate() {
+                let pos = BlockPos::new(x, *y, BRANCH_STEP * (j as i32 + 1));
+                branch_children[j * points / heights.len()]
+                    .insert(pos, params.block_map.get_block(&branch.block));
+            }
+
+            children.extend(branch_children.into_iter().map(|blocks| ChildGeneration {
+                blocks,
+                alt_blocks: HashMap::new(),
+                check_blocks: HashSet::new(),
+                reached: false,
+            }));
+        }
+
+        *ordered = false;
+
+        let exit = BlockPos::new(rng.gen_range(min_x..=max_x), 0, exit_z);
+        PredictionState::running_jump_block(self.start + exit, random_yaw())
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use valence::prelude::*;
+
+use crate::{
+    generation::{
+        generation::{AltBlock, AltBlockCondition},
+        generator::{BlockGenParams, Generator},
+    },
+    prediction::prediction_state::PredictionState,
+    utils::*,
+};
+
+/// The `BridgeGenParams` struct represents the parameters used to generate a
+/// bridge that appears over time.
+///
+/// Properties:
+///
+/// * `block`: The `block` property is the name of the block the platforms on
+/// both ends are made of.
+/// * `bridge`: The `bridge` property is the name of the block the bridge is made
+/// of.
+/// * `min_length`: The `min_length` property is the minimum length of the bridge.
+/// * `max_length`: The `max_length` property is the maximum length of the bridge.
+/// * `delay`: The `delay` property is the amount of ticks between each block of
+/// the bridge appearing.
+#[derive(Clone, Debug)]
+pub struct BridgeGenParams {
+    pub block: String,
+    pub bridge: String,
+    pub min_length: i32,
+    pub max_length: i32,
+    pub delay: usize,
+}
+
+impl Generator {
+    /// Generates a gap that is too long to jump over. Once the player reaches the
+    /// generation, a bridge appears over it one block at a time.
+    pub fn generate_bridge(
+        &self,
+        bridge: &BridgeGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+        alt_blocks: &mut HashMap<BlockPos, AltBlock>,
+    ) -> PredictionState {
+        let mut rng = rand::thread_rng();
+
+        let length = rng.gen_range(bridge.min_length..=bridge.max_length);
+
+        blocks.insert(
+            BlockPos::new(0, 0, 0),
+            params.block_map.get_block(&bridge.block),
+        );
+        for z in 1..=length {
+            alt_blocks.insert(
+                BlockPos::new(0, 0, z),
+                AltBlock::new(
+                    BlockState::AIR,
+                    params.block_map.get_block(&bridge.bridge),
+                    AltBlockCondition::Ticks(bridge.delay * z as usize),
+                ),
+            );
+        }
+
+        let end = BlockPos::new(0, 0, length + 1);
+        blocks.insert(end, params.block_map.get_block(&bridge.block));
+
+        PredictionState::running_jump_block(self.start + end, random_yaw())
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::{HashMap, HashSet};
+
+use noise::{NoiseFn, Perlin};
+use rand::Rng;
+use valence::prelude::*;
+
+use crate::{
+    generation::generator::{BlockGenParams, Generator},
+    prediction::prediction_state::PredictionState,
+    utils::*,
+};
+
+/// How zoomed in the noise used to carve the cave is.
+const NOISE_SCALE: f64 = 0.25;
+/// How much the noise changes the shape of the cave.
+const NOISE_STRENGTH: f64 = 1.2;
+/// The radius of the tunnel, before noise is applied.
+const RADIUS: f64 = 2.5;
+/// The height of the center of the tunnel above the platforms.
+const CENTER_Y: f64 = 3.;
+/// The size of the volume the cave is carved out of.
+const HALF_WIDTH: i32 = 5;
+const HEIGHT: i32 = 7;
+
+/// The `CaveGenParams` struct represents the parameters used to generate a cave.
+///
+/// Properties:
+///
+/// * `block`: The `block` property is the name of the block the cave is made of.
+/// * `platform`: The `platform` property is the name of the block used for the
+/// platforms the player jumps on.
+#[derive(Clone, Debug)]
+pub struct CaveGenParams {
+    pub block: String,
+    pub platform: String,
+}
+
+impl Generator {
+    /// Generates a tunnel carved out of a solid volume using 3D noise, with jumps
+    /// going through it. The floor of the tunnel is a chasm, so the player has to
+    /// jump between the platforms.
+    ///
+    /// Every block the player could touch while doing the jumps, as predicted by
+    /// the prediction physics, is kept clear. The walls next to the path are two
+    /// blocks high, so the player can't walk around the jumps.
+    pub fn generate_cave(
+        &self,
+        cave: &CaveGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+    ) -> PredictionState {
+        let mut rng = rand::thread_rng();
+        let perlin = Perlin::new(rng.gen());
+
+        let jumps = rng.gen_range(2..=3);
+
+        let mut platforms = vec![BlockPos::new(0, 0, 0)];
+        let mut clear = HashSet::new();
+        let mut takeoff = BlockPos::new(0, 0, 0);
+
+        for _ in 0..jumps {
+            let state = PredictionState::running_jump_block(takeoff, 0.);
+            let states = state.get_states_until_landed(takeoff.y as f64 + 1.);
+
+            for state in &states {
+                clear.extend(state.get_intersected_blocks());
+            }
+
+            takeoff = states.last().unwrap().get_block_pos();
+            platforms.push(takeoff);
+        }
+
+        for pos in &platforms {
+            let standing = PredictionState::new(
+                DVec3::new(pos.x as f64 + 0.5, pos.y as f64 + 1., pos.z as f64 + 0.5),
+                DVec3::ZERO,
+                0.,
+            );
+            clear.extend(standing.get_intersected_blocks());
+        }
+
+        let is_air = |pos: BlockPos| {
+            if clear.contains(&pos) {
+                return true;
+            }
+
+            // The bottom of the tunnel is only as wide as the path.
+            if pos.y <= 2 {
+                return pos.x.abs() <= 1;
+            }
+
+            let dx = pos.x as f64;
+            let dy = (pos.y as f64 - CENTER_Y) * 1.3;
+            let noise = perlin.get([
+                pos.x as f64 * NOISE_SCALE,
+                pos.y as f64 * NOISE_SCALE,
+                pos.z as f64 * NOISE_SCALE,
+            ]);
+
+            (dx * dx + dy * dy).sqrt() + noise * NOISE_STRENGTH < RADIUS
+        };
+
+        // The first and last platforms are outside of the cave, so the jumps into
+        // and out of it aren't blocked.
+        let end_z = takeoff.z;
+
+        for x in -HALF_WIDTH..=HALF_WIDTH {
+            for y in 1..=HEIGHT {
+                for z in 1..end_z {
+                    let pos = BlockPos::new(x, y, z);
+
+                    if is_air(pos) {
+                        continue;
+                    }
+
+                    // Only the blocks next to the tunnel are placed.
+                    let next_to_air = [
+                        BlockPos::new(x + 1, y, z),
+                        BlockPos::new(x - 1, y, z),
+                        BlockPos::new(x, y + 1, z),
+                        BlockPos::new(x, y - 1, z),
+                        BlockPos::new(x, y, z + 1),
+                        BlockPos::new(x, y, z - 1),
+                    ]
+                    .into_iter()
+                    .any(|pos| {
+                        pos.y >= 1
+                            && pos.z >= 1
+                            && pos.z < end_z
+                            && pos.x.abs() <= HALF_WIDTH
+                            && is_air(pos)
+                    });
+
+                    if next_to_air {
+                        blocks.insert(pos, params.block_map.get_block(&cave.block));
+                    }
+                }
+            }
+        }
+
+        for pos in platforms {
+            blocks.insert(pos, params.block_map.get_block(&cave.platform));
+        }
+
+        PredictionState::running_jump_block(self.start + takeoff, random_yaw_dist(30.))
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use valence::prelude::*;
+
+use crate::{
+    generation::generator::{BlockGenParams, Generator},
+    prediction::prediction_state::PredictionState,
+    utils::*,
+};
+
+/// The `ClimbGenParams` struct represents the parameters used to generate a
+/// climb.
+///
+/// Properties:
+///
+/// * `block`: The `block` property is the name of the block the platforms are
+/// made of.
+/// * `wall`: The `wall` property is the name of the block the column is made of.
+/// * `climbable`: The `climbable` property is the name of the block the player
+/// climbs. This should be a ladder or a vine.
+/// * `min_height`: The `min_height` property is the minimum height of the column.
+/// * `max_height`: The `max_height` property is the maximum height of the column.
+#[derive(Clone, Debug)]
+pub struct ClimbGenParams {
+    pub block: String,
+    pub wall: String,
+    pub climbable: String,
+    pub min_height: i32,
+    pub max_height: i32,
+}
+
+impl Generator {
+    /// Generates a column with a ladder or vine on it. The player climbs it, gets
+    /// off on top of the column, and jumps off of the other side.
+    pub fn generate_climb(
+        &self,
+        climb: &ClimbGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+    ) -> PredictionState {
+        let mut rng = rand::thread_rng();
+
+        let block = params.block_map.get_block(&climb.block);
+        let wall = params.block_map.get_block(&climb.wall);
+        let climbable = params.block_map.get_block(&climb.climbable);
+
+        // Ladders face away from the block they are on, vines have a property
+        // for every side they can be attached to.
+        let climbable = if climbable.get(PropName::Facing).is_some() {
+            climbable.set(PropName::Facing, PropValue::North)
+        } else {
+            climbable.set(PropName::South, PropValue::True)
+        };
+
+        let height = rng.gen_range(climb.min_height..=climb.max_height);
+
+        // The climb is built going south (+z) and rotated afterwards.
+        let mut segment = HashMap::new();
+        segment.insert(BlockPos::new(0, 0, 0), block);
+        segment.insert(BlockPos::new(0, 0, 1), block);
+
+        for y in 0..=height {
+            segment.insert(BlockPos::new(0, y, 2), wall);
+        }
+        for y in 1..=height {
+            segment.insert(BlockPos::new(0, y, 1), climbable);
+        }
+
+        let mut state = PredictionState::climb(BlockPos::new(0, 0, 1), (height + 1) as f64, 0.);
+        state.tick_until_landed((height + 1) as f64);
+
+        // The player gets off the ladder and lands on the top of the column, or a
+        // bit behind it. Everything from the column up to where they land is
+        // filled in, with one more block to run onto before they jump.
+        let top = state.get_block_pos();
+        let exit_z = top.z.max(2) + 1;
+        for z in 3..=exit_z {
+            segment.insert(BlockPos::new(top.x, top.y, z), block);
+        }
+        let mut pos = BlockPos::new(top.x, top.y, exit_z);
+
+        // Don't rotate 180 degrees, that would make the climb go back into the
+        // previous generations.
+        let rotations = [0, 1, 3][rng.gen_range(0..3)];
+        for _ in 0..rotations {
+            segment = rotate_block_map_cw(&segment, BlockPos::new(0, 0, 0));
+            pos = pos.rotate_cw(BlockPos::new(0, 0, 0));
+        }
+
+        blocks.extend(segment);
+
+        PredictionState::running_jump_block(self.start + pos, random_yaw())
+    }
+}
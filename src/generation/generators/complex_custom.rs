@@ -0,0 +1,138 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::{seq::SliceRandom, Rng};
+use valence::{math::IVec3, prelude::*};
+
+use crate::{
+    generation::{
+        custom_generation::{ComplexCustomPreset, ComplexCustomTile},
+        generator::Generator,
+    },
+    prediction::prediction_state::PredictionState,
+    utils::*,
+};
+
+impl Generator {
+    /// Generates a tile-based segment. A randomized DFS carves a maze through the
+    /// grid, and every cell then gets a tile whose sockets match the faces the maze
+    /// opened. The maze is entered in the middle of the first row and left from a
+    /// random cell in the last row.
+    pub fn generate_complex_custom(
+        &self,
+        preset: &ComplexCustomPreset,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+    ) -> PredictionState {
+        let mut rng = rand::thread_rng();
+
+        let half = preset.tile_size / 2;
+        let entry = BlockPos::new(preset.size.x / 2, 0, 0);
+        let exit = BlockPos::new(rng.gen_range(0..preset.size.x), 0, preset.size.z - 1);
+
+        let get_center = |cell: BlockPos| {
+            BlockPos::new(
+                (cell.x - entry.x) * preset.tile_size,
+                0,
+                1 + half + cell.z * preset.tile_size,
+            )
+        };
+
+        let mut open: HashMap<BlockPos, HashSet<BlockPos>> = HashMap::new();
+        open.entry(entry)
+            .or_default()
+            .insert(BlockPos::new(0, 0, -1));
+        open.entry(exit).or_default().insert(BlockPos::new(0, 0, 1));
+
+        let mut visited = HashSet::from([entry]);
+        let mut order = vec![entry];
+        let mut stack = vec![entry];
+
+        while let Some(&cell) = stack.last() {
+            let mut dirs = get_dirs_next_to(BlockPos::new(0, 1, 0));
+            dirs.shuffle(&mut rng);
+
+            let next = dirs.into_iter().find(|dir| {
+                let next = cell + *dir;
+                next.x >= 0
+                    && next.x < preset.size.x
+                    && next.z >= 0
+                    && next.z < preset.size.z
+                    && !visited.contains(&next)
+            });
+
+            match next {
+                Some(dir) => {
+                    let next = cell + dir;
+                    open.entry(cell).or_default().insert(dir);
+                    open.entry(next)
+                        .or_default()
+                        .insert(BlockPos::new(-dir.x, -dir.y, -dir.z));
+
+                    visited.insert(next);
+                    order.push(next);
+                    stack.push(next);
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+
+        let tiles = preset
+            .tiles
+            .iter()
+            .flat_map(|tile| tile.get_rotations())
+            .collect::<Vec<_>>();
+        let mut placed: HashMap<BlockPos, &ComplexCustomTile> = HashMap::new();
+
+        // The cells are filled in the order the DFS visited them, so every cell
+        // only has to match the neighbours that were filled before it.
+        for cell in order {
+            let faces = &open[&cell];
+
+            let candidates = tiles
+                .iter()
+                .filter(|tile| {
+                    tile.sockets.len() == faces.len()
+                        && faces.iter().all(|dir| match tile.sockets.get(dir) {
+                            Some(socket) => placed.get(&(cell + *dir)).map_or(true, |other| {
+                                other.sockets.get(&BlockPos::new(-dir.x, -dir.y, -dir.z))
+                                    == Some(socket)
+                            }),
+                            None => false,
+                        })
+                })
+                .collect::<Vec<_>>();
+
+            let tile = candidates.choose(&mut rng).unwrap_or_else(|| {
+                panic!(
+                    "No tile of preset `{}` fits the faces {:?}",
+                    preset.name, faces
+                )
+            });
+
+            let center = get_center(cell);
+            for (pos, block) in &tile.blocks {
+                blocks.insert(*pos + center, *block);
+            }
+
+            placed.insert(cell, tile);
+        }
+
+        // The block the player lands on before entering the first tile.
+        let entry_block = blocks[&BlockPos::new(0, 0, 1)];
+        blocks.insert(BlockPos::new(0, 0, 0), entry_block);
+
+        let exit_pos = get_center(exit) + BlockPos::new(0, 0, half);
+
+        let (min_yaw, max_yaw) = get_min_max_yaw(
+            BlockPos::new(exit_pos.x + entry.x * preset.tile_size + half, 0, 0),
+            &IVec3::new(
+                preset.size.x * preset.tile_size,
+                0,
+                preset.size.z * preset.tile_size,
+            ),
+        );
+
+        PredictionState::running_jump_block(self.start + exit_pos, rng.gen_range(min_yaw..=max_yaw))
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use valence::prelude::*;
+
+use crate::{
+    generation::{
+        generation::{AltBlock, AltBlockCondition},
+        generator::{BlockGenParams, Generator},
+    },
+    prediction::prediction_state::PredictionState,
+    utils::*,
+};
+
+/// The `CrumbleGenParams` struct represents the parameters used to generate
+/// blocks that crumble after the player stands on them.
+///
+/// Properties:
+///
+/// * `block`: The `block` property is the name of the block the player lands on
+/// first. It does not crumble.
+/// * `crumble`: The `crumble` property is the name of the block that crumbles.
+/// * `min_jumps`: The `min_jumps` property is the minimum amount of jumps.
+/// * `max_jumps`: The `max_jumps` property is the maximum amount of jumps.
+/// * `delay`: The `delay` property is the amount of ticks a block stays after the
+/// player first stands on it.
+#[derive(Clone, Debug)]
+pub struct CrumbleGenParams {
+    pub block: String,
+    pub crumble: String,
+    pub min_jumps: i32,
+    pub max_jumps: i32,
+    pub delay: usize,
+}
+
+impl Generator {
+    /// Generates a few jumps onto blocks that turn into air shortly after the
+    /// player stands on them, so the player has to keep moving.
+    pub fn generate_crumble(
+        &self,
+        crumble: &CrumbleGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+        alt_blocks: &mut HashMap<BlockPos, AltBlock>,
+    ) -> PredictionState {
+        let mut rng = rand::thread_rng();
+
+        let mut pos = BlockPos::new(0, 0, 0);
+        blocks.insert(pos, params.block_map.get_block(&crumble.block));
+
+        for _ in 0..rng.gen_range(crumble.min_jumps..=crumble.max_jumps) {
+            let y = pos.y + params.direction.get_y_offset();
+
+            let mut state = PredictionState::running_jump_block(pos, random_yaw_dist(30.));
+            state.tick_until_landed((y + 1) as f64);
+
+            pos = BlockPos::new(state.pos.x.floor() as i32, y, state.pos.z.floor() as i32);
+            alt_blocks.insert(
+                pos,
+                AltBlock::new(
+                    params.block_map.get_block(&crumble.crumble),
+                    BlockState::AIR,
+                    AltBlockCondition::SteppedOn(crumble.delay),
+                ),
+            );
+        }
+
+        PredictionState::running_jump_block(self.start + pos, random_yaw())
+    }
+}
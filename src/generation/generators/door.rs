@@ -0,0 +1,111 @@
+use std::collections::{HashMap, HashSet};
+
+use valence::prelude::*;
+
+use crate::{
+    generation::{
+        generation::{AltBlock, AltBlockCondition, ChildGeneration},
+        generator::{BlockGenParams, Generator},
+    },
+    prediction::prediction_state::PredictionState,
+    utils::*,
+};
+
+/// The distance from the start to the wall.
+const DOOR_Z: i32 = 2;
+
+/// The height of the wall and the doors.
+const DOOR_HEIGHT: i32 = 2;
+
+/// The distance from the middle of the floor to the button. There is a one block
+/// gap between the floor and the button.
+const BUTTON_X: i32 = 3;
+
+/// How far the wall goes out to each side. It reaches past the button, so the
+/// player can't get around it from there.
+const WALL_WIDTH: i32 = BUTTON_X;
+
+/// The `DoorGenParams` struct represents the parameters used to generate a wall
+/// with doors in it.
+///
+/// Properties:
+///
+/// * `block`: The `block` property is the name of the block the floor is made of.
+/// * `wall`: The `wall` property is the name of the block the wall is made of.
+/// * `door`: The `door` property is the name of the block the doors are made of.
+/// * `button`: The `button` property is the name of the block the player stands
+/// on to open the first door.
+/// * `combo`: The `combo` property is the combo the player needs to open the
+/// second door.
+#[derive(Clone, Debug)]
+pub struct DoorGenParams {
+    pub block: String,
+    pub wall: String,
+    pub door: String,
+    pub button: String,
+    pub combo: u32,
+}
+
+impl Generator {
+    /// Generates a floor with a wall across it, which has two doors in it. The
+    /// first door opens once the player has jumped onto the button next to the
+    /// floor, which is a child generation. The second door is already open if
+    /// the combo of the player is high enough, so they don't have to take the
+    /// detour.
+    pub fn generate_door(
+        &self,
+        door: &DoorGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+        alt_blocks: &mut HashMap<BlockPos, AltBlock>,
+        children: &mut Vec<ChildGeneration>,
+    ) -> PredictionState {
+        let side = random_sign();
+
+        for x in -1..=1 {
+            for z in 0..=DOOR_Z + 1 {
+                blocks.insert(
+                    BlockPos::new(x, 0, z),
+                    params.block_map.get_block(&door.block),
+                );
+            }
+        }
+
+        for x in -WALL_WIDTH..=WALL_WIDTH {
+            for y in 1..=DOOR_HEIGHT {
+                let pos = BlockPos::new(x, y, DOOR_Z);
+
+                let condition = if x == -side {
+                    AltBlockCondition::ChildReached(0)
+                } else if x == side {
+                    AltBlockCondition::Combo(door.combo)
+                } else {
+                    blocks.insert(pos, params.block_map.get_block(&door.wall));
+                    continue;
+                };
+
+                alt_blocks.insert(
+                    pos,
+                    AltBlock::new(
+                        params.block_map.get_block(&door.door),
+                        BlockState::AIR,
+                        condition,
+                    ),
+                );
+            }
+        }
+
+        children.push(ChildGeneration {
+            blocks: HashMap::from([(
+                BlockPos::new(-side * BUTTON_X, 0, 0),
+                params.block_map.get_block(&door.button),
+            )]),
+            alt_blocks: HashMap::new(),
+            check_blocks: HashSet::new(),
+            reached: false,
+        });
+
+        let end = BlockPos::new(0, 0, DOOR_Z + 1);
+        PredictionState::running_jump_block(self.start + end, random_yaw())
+    }
+}
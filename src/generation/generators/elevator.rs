@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use valence::prelude::*;
+
+use crate::{
+    generation::generator::{BlockGenParams, Generator},
+    prediction::{block_physics::Fluid, prediction_state::PredictionState},
+    utils::*,
+};
+
+/// The `ElevatorGenParams` struct represents the parameters used to generate an
+/// elevator.
+///
+/// Properties:
+///
+/// * `block`: The `block` property is the name of the block the platforms are
+/// made of.
+/// * `min_height`: The `min_height` property is the minimum height of the column.
+/// * `max_height`: The `max_height` property is the maximum height of the column.
+#[derive(Clone, Debug)]
+pub struct ElevatorGenParams {
+    pub block: String,
+    pub min_height: i32,
+    pub max_height: i32,
+}
+
+impl Generator {
+    /// Generates a column of water or bubbles that carries the player up or down
+    /// to the next jump. Columns going up are either water the player swims up
+    /// or a bubble column over soul sand. Columns going down are bubble columns
+    /// over magma.
+    pub fn generate_elevator(
+        &self,
+        elevator: &ElevatorGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+        fluids: &mut HashMap<BlockPos, BlockState>,
+    ) -> PredictionState {
+        let mut rng = rand::thread_rng();
+
+        let block = params.block_map.get_block(&elevator.block);
+        let height = rng.gen_range(elevator.min_height..=elevator.max_height);
+        let going_down = params.direction.go_down();
+
+        let (bottom_y, top_y, bottom, fluid) = if going_down {
+            (
+                -height,
+                1,
+                BlockState::MAGMA_BLOCK,
+                BlockState::BUBBLE_COLUMN.set(PropName::Drag, PropValue::True),
+            )
+        } else if rng.gen_bool(0.5) {
+            (
+                0,
+                height,
+                BlockState::SOUL_SAND,
+                BlockState::BUBBLE_COLUMN.set(PropName::Drag, PropValue::False),
+            )
+        } else {
+            (0, height, block, BlockState::WATER)
+        };
+
+        // The column is right in front of the start, so the player walks into it.
+        blocks.insert(BlockPos::new(0, 0, 0), block);
+        blocks.insert(BlockPos::new(0, bottom_y, 1), bottom);
+        for y in bottom_y + 1..=top_y {
+            fluids.insert(BlockPos::new(0, y, 1), fluid);
+        }
+
+        let get_fluid = |state: &PredictionState| {
+            let pos = state.get_block_pos();
+            let feet = BlockPos::new(pos.x, pos.y + 1, pos.z);
+            let above = BlockPos::new(pos.x, pos.y + 2, pos.z);
+
+            fluids.get(&feet).and_then(|block| {
+                Fluid::get(
+                    *block,
+                    fluids.get(&above).copied().unwrap_or(BlockState::AIR),
+                )
+            })
+        };
+
+        let mut state = PredictionState::standing(BlockPos::new(0, 0, 0), block, 0.);
+        while state.fluid.is_none() {
+            state.tick();
+            state.fluid = get_fluid(&state);
+        }
+        state.on_ground = false;
+
+        let (exit_y, exit_z) = if going_down {
+            // Ride the column down, and walk out at the bottom.
+            while state.pos.y > (bottom_y + 1) as f64 {
+                state.tick();
+                state.fluid = get_fluid(&state);
+            }
+
+            (bottom_y, 2)
+        } else {
+            // Ride the column up, and land wherever the player gets thrown out
+            // of the top.
+            while state.fluid.is_some() {
+                state.tick();
+                state.fluid = get_fluid(&state);
+            }
+            state.tick_until_landed((top_y + 1) as f64);
+
+            (top_y, state.get_block_pos().z.max(2))
+        };
+
+        for z in 2..=exit_z + 1 {
+            blocks.insert(BlockPos::new(0, exit_y, z), block);
+        }
+
+        PredictionState::running_jump_block(
+            self.start + BlockPos::new(0, exit_y, exit_z + 1),
+            random_yaw(),
+        )
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use valence::prelude::*;
+
+use crate::{
+    generation::generator::{BlockGenParams, Generator},
+    prediction::prediction_state::{PredictionState, HEAD_HIT_CEILING},
+    utils::*,
+};
+
+/// The `HeadHitGenParams` struct represents the parameters used to generate head
+/// hit jumps.
+///
+/// Properties:
+///
+/// * `block`: The `block` property is the name of the block the platforms are
+/// made of.
+/// * `ceiling`: The `ceiling` property is the name of the block placed over the
+/// takeoff spots.
+#[derive(Clone, Debug)]
+pub struct HeadHitGenParams {
+    pub block: String,
+    pub ceiling: String,
+}
+
+impl Generator {
+    /// Generates a few head hit jumps in a row. Every takeoff spot has a ceiling
+    /// block over it, and the landing spots come from the head hit trajectory.
+    pub fn generate_head_hit(
+        &self,
+        head_hit: &HeadHitGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+    ) -> PredictionState {
+        let mut rng = rand::thread_rng();
+
+        // The first platform is two blocks long, so the player doesn't come in
+        // through the first ceiling block.
+        blocks.insert(
+            BlockPos::new(0, 0, 0),
+            params.block_map.get_block(&head_hit.block),
+        );
+        let mut takeoff = BlockPos::new(0, 0, 1);
+
+        for _ in 0..rng.gen_range(2..=4) {
+            blocks.insert(takeoff, params.block_map.get_block(&head_hit.block));
+            blocks.insert(
+                BlockPos::new(takeoff.x, takeoff.y + HEAD_HIT_CEILING, takeoff.z),
+                params.block_map.get_block(&head_hit.ceiling),
+            );
+
+            let mut state = PredictionState::running_jump_ceiling(takeoff, random_yaw_dist(20.));
+            state.tick_until_landed(takeoff.y as f64 + 1.);
+
+            takeoff = state.get_block_pos();
+        }
+
+        blocks.insert(takeoff, params.block_map.get_block(&head_hit.block));
+
+        PredictionState::running_jump_block(self.start + takeoff, random_yaw())
+    }
+}
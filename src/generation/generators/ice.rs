@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use valence::prelude::*;
+
+use crate::{
+    generation::generator::{BlockGenParams, Generator},
+    prediction::{block_physics::BlockPhysics, prediction_state::PredictionState},
+    utils::*,
+};
+
+/// The `IceGenParams` struct represents the parameters used to generate an ice
+/// runway.
+///
+/// Properties:
+///
+/// * `ice`: The `ice` property is the name of the block the runway is made of.
+/// * `block`: The `block` property is the name of the block the landing is made
+/// of.
+/// * `min_length`: The `min_length` property is the minimum length of the runway.
+/// * `max_length`: The `max_length` property is the maximum length of the runway.
+#[derive(Clone, Debug)]
+pub struct IceGenParams {
+    pub ice: String,
+    pub block: String,
+    pub min_length: i32,
+    pub max_length: i32,
+}
+
+impl Generator {
+    /// Generates a slippery runway. The player builds up momentum on it, so the
+    /// landing is further away than it would be after a normal jump.
+    pub fn generate_ice(
+        &self,
+        ice: &IceGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+    ) -> PredictionState {
+        let mut rng = rand::thread_rng();
+
+        let length = rng.gen_range(ice.min_length..=ice.max_length);
+        for z in 0..length {
+            blocks.insert(BlockPos::new(0, 0, z), params.block_map.get_block(&ice.ice));
+        }
+
+        // Run from a standstill to the end of the runway. The player could be
+        // going faster than this, but they can always slow down.
+        let start = BlockPos::new(0, 0, 0);
+        let mut state = PredictionState::standing(start, blocks[&start], 0.);
+
+        loop {
+            let mut new_state = state;
+            new_state.tick();
+
+            if new_state.pos.z >= length as f64 {
+                break;
+            }
+
+            state = new_state;
+            state.ground = BlockPhysics::get(blocks[&state.get_block_pos()]);
+        }
+
+        state.jump();
+        state.tick_until_landed((1 + params.direction.get_y_offset()) as f64);
+
+        let landing = state.get_block_pos();
+        let end = BlockPos::new(landing.x, landing.y, landing.z + 1);
+        blocks.insert(landing, params.block_map.get_block(&ice.block));
+        blocks.insert(end, params.block_map.get_block(&ice.block));
+
+        PredictionState::running_jump_block(self.start + end, random_yaw())
+    }
+}
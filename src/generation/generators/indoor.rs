@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use valence::prelude::*;
+
+use crate::{
+    generation::generator::{BlockGenParams, Generator},
+    prediction::prediction_state::{PredictionState, HEAD_HIT_CEILING},
+    utils::*,
+};
+
+/// The `IndoorGenParams` struct represents the parameters used to generate an
+/// indoor area.
+///
+/// Properties:
+///
+/// * `floor`: The `floor` property is the name of the block used for the
+/// platforms the player jumps on.
+/// * `wall`: The `wall` property is the name of the block used for the walls.
+/// * `ceiling`: The `ceiling` property is the name of the block used for the
+/// ceiling.
+#[derive(Clone, Debug)]
+pub struct IndoorGenParams {
+    pub floor: String,
+    pub wall: String,
+    pub ceiling: String,
+}
+
+impl Generator {
+    /// Generates a room with walls and a ceiling, with jumps between platforms
+    /// inside of it. Both ends of the room are open, so the player can always get
+    /// in and out.
+    ///
+    /// Half of the rooms have a low ceiling, which makes every jump inside of
+    /// them a head hit jump. The other half have a ceiling that is high enough to
+    /// never be hit.
+    pub fn generate_indoor(
+        &self,
+        indoor: &IndoorGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+    ) -> PredictionState {
+        let mut rng = rand::thread_rng();
+
+        let low_ceiling = rng.gen_bool(0.5);
+        let jumps = rng.gen_range(2..=3);
+
+        // The first platform is two blocks long, so the player can land outside
+        // of the room and jump off from under the ceiling.
+        let mut platforms = vec![BlockPos::new(0, 0, 0), BlockPos::new(0, 0, 1)];
+        let mut takeoff = BlockPos::new(0, 0, 1);
+
+        for _ in 0..jumps {
+            let mut state = if low_ceiling {
+                PredictionState::head_hit_jump(takeoff, 0.)
+            } else {
+                PredictionState::running_jump_block(takeoff, 0.)
+            };
+
+            state.tick_until_landed(takeoff.y as f64 + 1.);
+
+            takeoff = state.get_block_pos();
+            platforms.push(takeoff);
+        }
+
+        let ceiling_y = if low_ceiling {
+            // The player is almost two blocks tall, so they hit their head as
+            // soon as they jump.
+            HEAD_HIT_CEILING
+        } else {
+            let mut state = PredictionState::running_jump_block(BlockPos::new(0, 0, 0), 0.);
+            while state.vel.y > 0. {
+                state.tick();
+            }
+
+            (state.pos.y + PLAYER_HEIGHT).ceil() as i32
+        };
+
+        let end_z = takeoff.z;
+
+        for z in 0..=end_z {
+            for y in 0..=ceiling_y {
+                blocks.insert(
+                    BlockPos::new(-2, y, z),
+                    params.block_map.get_block(&indoor.wall),
+                );
+                blocks.insert(
+                    BlockPos::new(2, y, z),
+                    params.block_map.get_block(&indoor.wall),
+                );
+            }
+
+            // The first and last rows don't have a ceiling, so the jumps into
+            // and out of the room don't hit it.
+            if z == 0 || z == end_z {
+                continue;
+            }
+
+            for x in -2..=2 {
+                blocks.insert(
+                    BlockPos::new(x, ceiling_y, z),
+                    params.block_map.get_block(&indoor.ceiling),
+                );
+            }
+        }
+
+        for pos in platforms {
+            blocks.insert(pos, params.block_map.get_block(&indoor.floor));
+        }
+
+        PredictionState::running_jump_block(self.start + takeoff, random_yaw_dist(30.))
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use noise::{NoiseFn, Perlin};
+use rand::Rng;
+use valence::prelude::*;
+
+use crate::{
+    generation::generator::{BlockGenParams, Generator},
+    prediction::prediction_state::PredictionState,
+    utils::*,
+};
+
+/// How zoomed in the noise used for the shape of the island is.
+const NOISE_SCALE: f64 = 0.35;
+
+/// The `IslandGenParams` struct represents the parameters used to generate an
+/// island.
+///
+/// Properties:
+///
+/// * `top`: The `top` property is the name of the block used for the top layer
+/// of the island.
+/// * `filler`: The `filler` property is the name of the block used for the
+/// inside of the island.
+/// * `bottom`: The `bottom` property is the name of the block used for the
+/// underside of the island.
+/// * `min_radius`: The `min_radius` property is the smallest radius an island can
+/// have.
+/// * `max_radius`: The `max_radius` property is the largest radius an island can
+/// have.
+#[derive(Clone, Debug)]
+pub struct IslandGenParams {
+    pub top: String,
+    pub filler: String,
+    pub bottom: String,
+    pub min_radius: i32,
+    pub max_radius: i32,
+}
+
+impl Generator {
+    /// Generates a small floating island with a noisy outline and underside.
+    ///
+    /// The player lands at the back of the island (the origin) and jumps off at
+    /// the front. The line between those two is always solid, so the island can
+    /// be crossed without jumping.
+    pub fn generate_island(
+        &self,
+        island: &IslandGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+    ) -> PredictionState {
+        let mut rng = rand::thread_rng();
+        let perlin = Perlin::new(rng.gen());
+
+        let radius = rng.gen_range(island.min_radius..=island.max_radius);
+        let length = radius * 2;
+
+        for x in -radius - 1..=radius + 1 {
+            for z in 0..=length {
+                let dx = x as f64;
+                let dz = (z - radius) as f64;
+                let dist = (dx * dx + dz * dz).sqrt() / (radius as f64 + 0.5);
+
+                let outline = perlin.get([x as f64 * NOISE_SCALE, z as f64 * NOISE_SCALE]);
+                let on_path = x == 0;
+
+                // Nothing next to the exit, so the next jump doesn't clip the island.
+                if !on_path && (z == length || dist + outline * 0.4 > 1.) {
+                    continue;
+                }
+
+                let underside = perlin.get([x as f64 * NOISE_SCALE, z as f64 * NOISE_SCALE, 10.]);
+                let depth = ((1. - dist).max(0.) * radius as f64 * 1.5 + underside * 1.5)
+                    .round()
+                    .max(1.) as i32;
+
+                blocks.insert(
+                    BlockPos::new(x, 0, z),
+                    params.block_map.get_block(&island.top),
+                );
+                for y in 1..depth {
+                    blocks.insert(
+                        BlockPos::new(x, -y, z),
+                        params.block_map.get_block(&island.filler),
+                    );
+                }
+                blocks.insert(
+                    BlockPos::new(x, -depth, z),
+                    params.block_map.get_block(&island.bottom),
+                );
+            }
+        }
+
+        PredictionState::running_jump_block(self.start + BlockPos::new(0, 0, length), random_yaw())
+    }
+}
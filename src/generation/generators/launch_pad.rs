@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use valence::prelude::*;
+
+use crate::{
+    generation::generator::{BlockGenParams, Generator},
+    prediction::prediction_state::PredictionState,
+    utils::*,
+};
+
+/// The `LaunchPadGenParams` struct represents the parameters used to generate a
+/// launch pad.
+///
+/// Properties:
+///
+/// * `block`: The `block` property is the name of the block the platforms are
+/// made of.
+/// * `pad`: The `pad` property is the name of the block the launch pad is made
+/// of.
+/// * `min_velocity`: The `min_velocity` property is the minimum upwards velocity
+/// the launch pad gives the player, in blocks per tick.
+/// * `max_velocity`: The `max_velocity` property is the maximum upwards velocity
+/// the launch pad gives the player, in blocks per tick.
+/// * `speed`: The `speed` property is the forwards velocity the launch pad gives
+/// the player, in blocks per tick.
+#[derive(Clone, Debug)]
+pub struct LaunchPadGenParams {
+    pub block: String,
+    pub pad: String,
+    pub min_velocity: f64,
+    pub max_velocity: f64,
+    pub speed: f64,
+}
+
+impl Generator {
+    /// Generates a launch pad that throws the player into a long jump. The landing
+    /// is found by predicting the jump from the velocity the pad gives, and is
+    /// made a bit bigger, since the player has less control over a jump like this.
+    pub fn generate_launch_pad(
+        &self,
+        launch_pad: &LaunchPadGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+        launch_pads: &mut HashMap<BlockPos, DVec3>,
+    ) -> PredictionState {
+        let mut rng = rand::thread_rng();
+
+        let pad = BlockPos::new(0, 0, 1);
+        blocks.insert(
+            BlockPos::new(0, 0, 0),
+            params.block_map.get_block(&launch_pad.block),
+        );
+        blocks.insert(pad, params.block_map.get_block(&launch_pad.pad));
+
+        let yaw = random_yaw_dist(30.);
+        let velocity = DVec3::new(
+            -launch_pad.speed * yaw.sin() as f64,
+            rng.gen_range(launch_pad.min_velocity..=launch_pad.max_velocity),
+            launch_pad.speed * yaw.cos() as f64,
+        );
+        launch_pads.insert(pad, velocity);
+
+        // The player is launched as soon as their hitbox touches the pad, but the
+        // server might only see them there once they are further onto it. The
+        // landing covers every launch from the edge of the pad up to its center.
+        let y = params.direction.get_y_offset();
+        let [near, far] = [1. - PLAYER_WIDTH / 2., 1.5].map(|z| {
+            let mut state = PredictionState::new(DVec3::new(0.5, 1., z), velocity, yaw);
+            state.tick_until_landed((y + 1) as f64);
+
+            BlockPos::new(state.pos.x.floor() as i32, y, state.pos.z.floor() as i32)
+        });
+
+        for x in near.x.min(far.x) - 1..=near.x.max(far.x) + 1 {
+            for z in near.z.min(far.z) - 1..=near.z.max(far.z) + 1 {
+                blocks.insert(
+                    BlockPos::new(x, y, z),
+                    params.block_map.get_block(&launch_pad.block),
+                );
+            }
+        }
+
+        PredictionState::running_jump_block(self.start + far, random_yaw())
+    }
+}
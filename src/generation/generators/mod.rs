@@ -0,0 +1,23 @@
+pub mod blink_blocks;
+pub mod branch;
+pub mod bridge;
+pub mod cave;
+pub mod climb;
+pub mod complex_custom;
+pub mod crumble;
+pub mod door;
+pub mod elevator;
+pub mod head_hit;
+pub mod ice;
+pub mod indoor;
+pub mod island;
+pub mod launch_pad;
+pub mod multi_custom;
+pub mod neo;
+pub mod partial;
+pub mod platform;
+pub mod ramp;
+pub mod single_custom;
+pub mod slime;
+pub mod slowdown;
+pub mod snake;
@@ -0,0 +1,21 @@
+use std::collections::HashMap;
+
+use valence::prelude::*;
+
+use crate::{
+    generation::{custom_generation::MultiCustomPreset, generator::Generator},
+    prediction::prediction_state::PredictionState,
+};
+
+impl Generator {
+    /// Generates a hand-built segment made out of multiple pieces. The pieces are
+    /// stitched together first, and the result is then placed like a
+    /// `SingleCustom` segment.
+    pub fn generate_multi_custom(
+        &self,
+        preset: &MultiCustomPreset,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+    ) -> PredictionState {
+        self.generate_single_custom(&preset.build(), blocks)
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::{seq::SliceRandom, Rng};
+use valence::prelude::*;
+
+use crate::{
+    generation::generator::{BlockGenParams, Generator},
+    prediction::prediction_state::PredictionState,
+    utils::*,
+};
+
+/// The z position of the wall, relative to the takeoff.
+const WALL_Z: i32 = 2;
+/// The height of the wall. It is too high to jump over.
+const WALL_HEIGHT: i32 = 3;
+
+/// The `NeoGenParams` struct represents the parameters used to generate a neo
+/// jump.
+///
+/// Properties:
+///
+/// * `block`: The `block` property is the name of the block the platforms are
+/// made of.
+/// * `wall`: The `wall` property is the name of the block the wall is made of.
+#[derive(Clone, Debug)]
+pub struct NeoGenParams {
+    pub block: String,
+    pub wall: String,
+}
+
+impl Generator {
+    /// Generates a neo jump. A wall blocks the straight line between the takeoff
+    /// and the landing, so the player has to jump out past the end of the wall
+    /// and turn back in mid-air.
+    ///
+    /// Returns `None` if no curve around the wall lands behind it.
+    pub fn generate_neo(
+        &self,
+        neo: &NeoGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+    ) -> Option<PredictionState> {
+        let mut rng = rand::thread_rng();
+
+        let side = random_sign();
+        let takeoff = BlockPos::new(0, 0, 0);
+        let wall_width = rng.gen_range(2..=3);
+
+        // The wall ends right in front of the takeoff, and goes away from the
+        // side the player curves around.
+        let mut wall = HashSet::new();
+        for i in 0..wall_width {
+            for y in 1..=WALL_HEIGHT {
+                wall.insert(BlockPos::new(-side * i, y, WALL_Z));
+            }
+        }
+
+        let mut landings = Vec::new();
+
+        for out_degrees in (10..=50).step_by(5) {
+            for in_degrees in (20..=60).step_by(10) {
+                for turn_tick in 1..=8 {
+                    let out_yaw = -side as f32 * (out_degrees as f32).to_radians();
+                    let in_yaw = side as f32 * (in_degrees as f32).to_radians();
+
+                    if let Some(landing) =
+                        get_neo_landing(takeoff, out_yaw, in_yaw, turn_tick, &wall)
+                    {
+                        landings.push(landing);
+                    }
+                }
+            }
+        }
+
+        // If there is no way around the wall, the neo jump can't be generated
+        // here.
+        let landing = landings.choose(&mut rng).copied()?;
+
+        blocks.insert(takeoff, params.block_map.get_block(&neo.block));
+        for pos in wall {
+            blocks.insert(pos, params.block_map.get_block(&neo.wall));
+        }
+        blocks.insert(landing, params.block_map.get_block(&neo.block));
+
+        Some(PredictionState::running_jump_block(
+            self.start + landing,
+            random_yaw_dist(30.),
+        ))
+    }
+}
+
+/// Simulates a neo jump off of `takeoff` that starts out looking towards
+/// `out_yaw` and turns to `in_yaw` after `turn_tick` ticks.
+///
+/// Returns the block the player lands on, if they get around the wall, land
+/// behind it, and the straight line to the landing goes through the wall.
+fn get_neo_landing(
+    takeoff: BlockPos,
+    out_yaw: f32,
+    in_yaw: f32,
+    turn_tick: usize,
+    wall: &HashSet<BlockPos>,
+) -> Option<BlockPos> {
+    let mut state = PredictionState::running_jump_block(takeoff, out_yaw);
+    let target_y = takeoff.y as f64 + 1.;
+
+    for tick in 0.. {
+        let mut new_state = state;
+        new_state.tick_yaw(if tick < turn_tick { out_yaw } else { in_yaw });
+
+        if hitbox_hits_blocks_between(state.pos, new_state.pos, wall) {
+            return None;
+        }
+
+        if new_state.vel.y <= 0. && new_state.pos.y <= target_y {
+            break;
+        }
+
+        state = new_state;
+    }
+
+    let landing = state.get_block_pos();
+    if landing.z <= WALL_Z {
+        return None;
+    }
+
+    let straight_line = get_blocks_between(
+        takeoff.to_vec3() + Vec3::new(0.5, 1.5, 0.5),
+        landing.to_vec3() + Vec3::new(0.5, 1.5, 0.5),
+    );
+    if !straight_line.iter().any(|pos| wall.contains(pos)) {
+        return None;
+    }
+
+    Some(landing)
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use valence::prelude::*;
+
+use crate::{
+    generation::generator::{BlockGenParams, Generator},
+    prediction::{
+        collision_shapes::get_block_top, prediction_state::PredictionState,
+        simulator::is_jump_clear,
+    },
+    utils::*,
+};
+
+/// How many yaws are tried for a jump before giving up on the generation.
+const MAX_ATTEMPTS: usize = 5;
+
+/// The `PartialGenParams` struct represents the parameters used to generate
+/// jumps onto blocks that are not full cubes.
+///
+/// Properties:
+///
+/// * `block`: The `block` property is the name of the block the player jumps
+/// off of first.
+/// * `partial`: The `partial` property is the name of the block the player lands
+/// on, like a trapdoor, fence, wall, slab or head.
+/// * `min_jumps`: The `min_jumps` property is the minimum amount of jumps.
+/// * `max_jumps`: The `max_jumps` property is the maximum amount of jumps.
+#[derive(Clone, Debug)]
+pub struct PartialGenParams {
+    pub block: String,
+    pub partial: String,
+    pub min_jumps: i32,
+    pub max_jumps: i32,
+}
+
+impl Generator {
+    /// Generates a few jumps onto blocks with a partial collision shape. The
+    /// height of every landing is picked so that its top is at most one block
+    /// above the top of the block the player jumps off of.
+    ///
+    /// Narrow blocks, like fences, are easy to jump past or into the side of, so
+    /// every jump is checked with the simulator against the blocks placed so far.
+    /// Returns `None` if none of the yaws tried for a jump land on the block.
+    pub fn generate_partial(
+        &self,
+        partial: &PartialGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+    ) -> Option<PredictionState> {
+        let mut rng = rand::thread_rng();
+
+        let mut pos = BlockPos::new(0, 0, 0);
+        let mut block = params.block_map.get_block(&partial.block);
+        blocks.insert(pos, block);
+
+        for _ in 0..rng.gen_range(partial.min_jumps..=partial.max_jumps) {
+            let new_block = params.block_map.get_block(&partial.partial);
+            let top = pos.y as f64 + get_block_top(block);
+            let new_top = get_block_top(new_block);
+
+            let max_y = (top + 1. - new_top).floor() as i32;
+            let y = (pos.y + params.direction.get_y_offset()).min(max_y);
+
+            let target_y = y as f64 + new_top;
+
+            let landing = (0..MAX_ATTEMPTS).find_map(|_| {
+                let jump = PredictionState::running_jump_shape(pos, block, random_yaw_dist(30.));
+                let mut state = jump;
+                state.tick_until_landed(target_y);
+
+                let landing =
+                    BlockPos::new(state.pos.x.floor() as i32, y, state.pos.z.floor() as i32);
+
+                let mut world = blocks.clone();
+                world.insert(landing, new_block);
+                is_jump_clear(jump, target_y, &world).then_some(landing)
+            })?;
+
+            pos = landing;
+            block = new_block;
+            blocks.insert(pos, block);
+        }
+
+        Some(PredictionState::running_jump_shape(
+            self.start + pos,
+            block,
+            random_yaw(),
+        ))
+    }
+}
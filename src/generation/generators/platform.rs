@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use valence::prelude::*;
+
+use crate::{
+    generation::{
+        generation::MovingPlatform,
+        generator::{BlockGenParams, Generator},
+    },
+    prediction::prediction_state::PredictionState,
+    utils::*,
+};
+
+/// The `PlatformGenParams` struct represents the parameters used to generate a
+/// moving platform.
+///
+/// Properties:
+///
+/// * `block`: The `block` property is the name of the block the platforms on
+/// both ends are made of.
+/// * `platform`: The `platform` property is the name of the block the moving
+/// platform looks like.
+/// * `min_distance`: The `min_distance` property is the minimum distance the
+/// platform moves.
+/// * `max_distance`: The `max_distance` property is the maximum distance the
+/// platform moves.
+/// * `ticks_per_block`: The `ticks_per_block` property is the amount of ticks it
+/// takes the platform to move one block.
+#[derive(Clone, Debug)]
+pub struct PlatformGenParams {
+    pub block: String,
+    pub platform: String,
+    pub min_distance: i32,
+    pub max_distance: i32,
+    pub ticks_per_block: usize,
+}
+
+impl Generator {
+    /// Generates a gap that is too long to jump over, with a platform that moves
+    /// back and forth over it. The platform stops one block away from both ends,
+    /// so the player can hop on and off of it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the platform moves in 0 ticks.
+    pub fn generate_platform(
+        &self,
+        platform: &PlatformGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+        platforms: &mut Vec<MovingPlatform>,
+    ) -> PredictionState {
+        let mut rng = rand::thread_rng();
+
+        let distance = rng.gen_range(platform.min_distance..=platform.max_distance);
+        let ticks = platform.ticks_per_block * distance as usize;
+        assert!(ticks > 0, "A moving platform can't move in 0 ticks");
+
+        blocks.insert(
+            BlockPos::new(0, 0, 0),
+            params.block_map.get_block(&platform.block),
+        );
+        platforms.push(MovingPlatform::new(
+            DVec3::new(0., 0., 2.),
+            DVec3::new(0., 0., (2 + distance) as f64),
+            params.block_map.get_block(&platform.platform),
+            ticks,
+            rng.gen_range(0..ticks * 2),
+        ));
+
+        let end = BlockPos::new(0, 0, 4 + distance);
+        blocks.insert(end, params.block_map.get_block(&platform.block));
+
+        PredictionState::running_jump_block(self.start + end, random_yaw())
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use valence::prelude::*;
+
+use crate::{
+    generation::generator::{BlockGenParams, Generator},
+    prediction::prediction_state::PredictionState,
+    utils::*,
+};
+
+impl Generator {
+    /// Generates a ramp made out of full blocks, slabs and stairs. The ramp goes
+    /// up or down depending on the `direction` in the `BlockGenParams`.
+    ///
+    /// The blocks are taken from the `"<key>_full"`, `"<key>_slab"` and
+    /// `"<key>_stair"` collections of the block map.
+    pub fn generate_ramp(
+        &self,
+        key: &str,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+    ) -> PredictionState {
+        let mut rng = rand::thread_rng();
+
+        let full = params.block_map.get_block(&format!("{}_full", key));
+        let slab = params
+            .block_map
+            .get_block(&format!("{}_slab", key))
+            .set(PropName::Type, PropValue::Bottom);
+        let stair = params
+            .block_map
+            .get_block(&format!("{}_stair", key))
+            .set(PropName::Half, PropValue::Bottom)
+            .set(PropName::Shape, PropValue::Straight);
+
+        let going_up = params.direction.go_up();
+        let steps = rng.gen_range(2..=4);
+
+        // The ramp is built going south (+z) and rotated afterwards.
+        let mut ramp = HashMap::new();
+        let mut pos = BlockPos::new(0, 0, 0);
+        ramp.insert(pos, full);
+
+        for _ in 0..steps {
+            let use_stair = rng.gen_bool(0.5);
+
+            if going_up {
+                if use_stair {
+                    pos = BlockPos::new(pos.x, pos.y + 1, pos.z + 1);
+                    ramp.insert(pos, stair.set(PropName::Facing, PropValue::South));
+                } else {
+                    ramp.insert(BlockPos::new(pos.x, pos.y + 1, pos.z + 1), slab);
+                    pos = BlockPos::new(pos.x, pos.y + 1, pos.z + 2);
+                    ramp.insert(pos, full);
+                }
+            } else {
+                let step = if use_stair {
+                    stair.set(PropName::Facing, PropValue::North)
+                } else {
+                    slab
+                };
+                ramp.insert(BlockPos::new(pos.x, pos.y, pos.z + 1), step);
+                pos = BlockPos::new(pos.x, pos.y - 1, pos.z + 2);
+                ramp.insert(pos, full);
+            }
+        }
+
+        // One more full block to jump off of.
+        pos = BlockPos::new(pos.x, pos.y, pos.z + 1);
+        ramp.insert(pos, full);
+
+        // Don't rotate 180 degrees, that would make the ramp go back into the
+        // previous generations.
+        let rotations = [0, 1, 3][rng.gen_range(0..3)];
+        for _ in 0..rotations {
+            ramp = rotate_block_map_cw(&ramp, BlockPos::new(0, 0, 0));
+            pos = pos.rotate_cw(BlockPos::new(0, 0, 0));
+        }
+
+        blocks.extend(ramp);
+
+        PredictionState::running_jump_block(self.start + pos, random_yaw())
+    }
+}
@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+
+use rand::seq::SliceRandom;
+use valence::prelude::*;
+
+use crate::{
+    generation::{custom_generation::SingleCustomPreset, generator::Generator},
+    prediction::prediction_state::PredictionState,
+    utils::*,
+};
+
+impl Generator {
+    /// Generates a random variant of a hand-built segment. The segment can be
+    /// rotated and flipped, as long as it still goes forward.
+    pub fn generate_single_custom(
+        &self,
+        preset: &SingleCustomPreset,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+    ) -> PredictionState {
+        let variants = preset.get_variants();
+        let variant = variants
+            .choose(&mut rand::thread_rng())
+            .unwrap_or_else(|| panic!("No valid variants of preset `{}`", preset.name));
+
+        blocks.extend(variant.blocks.iter());
+
+        PredictionState::running_jump_block(self.start + variant.end_pos, random_yaw())
+    }
+}
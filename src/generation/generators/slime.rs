@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use valence::prelude::*;
+
+use crate::{
+    generation::generator::{BlockGenParams, Generator},
+    prediction::{block_physics::BlockPhysics, prediction_state::PredictionState},
+    utils::*,
+};
+
+/// The `SlimeGenParams` struct represents the parameters used to generate a slime
+/// bounce.
+///
+/// Properties:
+///
+/// * `block`: The `block` property is the name of the block the platforms are
+/// made of.
+/// * `slime`: The `slime` property is the name of the block the player bounces on.
+/// * `min_drop`: The `min_drop` property is the minimum amount of blocks the slime
+/// is below the start.
+/// * `max_drop`: The `max_drop` property is the maximum amount of blocks the slime
+/// is below the start.
+#[derive(Clone, Debug)]
+pub struct SlimeGenParams {
+    pub block: String,
+    pub slime: String,
+    pub min_drop: i32,
+    pub max_drop: i32,
+}
+
+impl Generator {
+    /// Generates a drop onto a slime pad. The player bounces off of it to a
+    /// platform that is higher or farther away than a normal jump could reach.
+    pub fn generate_slime(
+        &self,
+        slime: &SlimeGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+    ) -> PredictionState {
+        let mut rng = rand::thread_rng();
+
+        let start_block = params.block_map.get_block(&slime.block);
+        blocks.insert(BlockPos::new(0, 0, 0), start_block);
+
+        let mut state = PredictionState::running_jump_block(self.start, random_yaw_dist(30.));
+        state.vel.y *= BlockPhysics::get(start_block).jump_factor;
+
+        // Find where the player falls onto the slime. The pad is 3x3, so it
+        // still gets hit if the player doesn't jump exactly as predicted.
+        let slime_y = -rng.gen_range(slime.min_drop..=slime.max_drop);
+        let slime_top = (self.start.y + slime_y + 1) as f64;
+
+        state.tick_until_landed(slime_top);
+        let mut hit = state;
+        hit.tick();
+
+        let slime_block = params.block_map.get_block(&slime.slime);
+        for x in -1..=1 {
+            for z in -1..=1 {
+                blocks.insert(
+                    BlockPos::new(
+                        hit.pos.x.floor() as i32 - self.start.x + x,
+                        slime_y,
+                        hit.pos.z.floor() as i32 - self.start.z + z,
+                    ),
+                    slime_block,
+                );
+            }
+        }
+
+        state.bounce_y = Some(slime_top);
+        while state.bounce_y.is_some() {
+            state.tick();
+        }
+
+        // The target has to be low enough for the player to land on it from the
+        // top of the bounce.
+        let mut apex = state;
+        while apex.vel.y > 0. {
+            apex.tick();
+        }
+
+        let max_y = (apex.pos.y - 1.5).floor() as i32 - self.start.y;
+        let min_y = max_y.min(-1);
+        let target_y = match params.direction {
+            JumpDirection::Up => max_y,
+            JumpDirection::Down => min_y,
+            JumpDirection::DoesntMatter => rng.gen_range(min_y..=max_y),
+        };
+
+        state.tick_until_landed((self.start.y + target_y + 1) as f64);
+        let mut end = state;
+        end.tick();
+
+        let block = params.block_map.get_block(&slime.block);
+        for landing in [state, end] {
+            blocks.insert(
+                BlockPos::new(
+                    landing.pos.x.floor() as i32 - self.start.x,
+                    target_y,
+                    landing.pos.z.floor() as i32 - self.start.z,
+                ),
+                block,
+            );
+        }
+
+        PredictionState::running_jump_block(
+            BlockPos::new(
+                end.pos.x.floor() as i32,
+                self.start.y + target_y,
+                end.pos.z.floor() as i32,
+            ),
+            random_yaw(),
+        )
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use valence::prelude::*;
+
+use crate::{
+    generation::generator::{BlockGenParams, Generator},
+    prediction::{block_physics::BlockPhysics, prediction_state::PredictionState},
+    utils::*,
+};
+
+/// The `SlowdownGenParams` struct represents the parameters used to generate a
+/// slowdown surface.
+///
+/// Properties:
+///
+/// * `block`: The `block` property is the name of the block the platforms are
+/// made of.
+/// * `surface`: The `surface` property is the name of the block that slows the
+/// player down. Blocks the player gets stuck in, like cobwebs, are placed on top
+/// of the platforms. Other blocks, like soul sand and honey, are the platforms.
+/// * `min_length`: The `min_length` property is the minimum length of the surface.
+/// * `max_length`: The `max_length` property is the maximum length of the surface.
+#[derive(Clone, Debug)]
+pub struct SlowdownGenParams {
+    pub block: String,
+    pub surface: String,
+    pub min_length: i32,
+    pub max_length: i32,
+}
+
+impl Generator {
+    /// Generates a strip of a surface that changes the speed or jump height of
+    /// the player, followed by a jump. The gap is picked from the gaps the player
+    /// can still make after crossing the surface.
+    ///
+    /// Returns `None` if the player is slowed down too much to jump over a gap.
+    pub fn generate_slowdown(
+        &self,
+        slowdown: &SlowdownGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+    ) -> Option<PredictionState> {
+        let mut rng = rand::thread_rng();
+
+        let block = params.block_map.get_block(&slowdown.block);
+        let surface = params.block_map.get_block(&slowdown.surface);
+        let is_stuck_block = BlockPhysics::get(surface).stuck_multiplier.is_some();
+
+        let length = rng.gen_range(slowdown.min_length..=slowdown.max_length);
+
+        let mut floor = HashMap::from([(BlockPos::new(0, 0, 0), block)]);
+        let mut inside = HashMap::new();
+
+        for z in 1..=length {
+            if is_stuck_block {
+                floor.insert(BlockPos::new(0, 0, z), block);
+                inside.insert(BlockPos::new(0, 1, z), surface);
+            } else {
+                floor.insert(BlockPos::new(0, 0, z), surface);
+            }
+        }
+
+        // Nobody can jump out of a cobweb, so there's a normal block after it.
+        // Other surfaces are jumped off of, so their jump factor is used.
+        let takeoff_z = if is_stuck_block {
+            floor.insert(BlockPos::new(0, 0, length + 1), block);
+            length + 1
+        } else {
+            length
+        };
+
+        // Run from the start to the end of the takeoff block, looking up the
+        // block under and inside of the player every tick.
+        let mut state = PredictionState::standing(BlockPos::new(0, 0, 0), block, 0.);
+
+        loop {
+            let mut new_state = state;
+            new_state.tick();
+
+            if new_state.pos.z >= (takeoff_z + 1) as f64 {
+                break;
+            }
+
+            state = new_state;
+
+            let pos = state.get_block_pos();
+            state.ground = BlockPhysics::get(floor[&pos]);
+            state.stuck_multiplier = inside
+                .get(&BlockPos::new(pos.x, pos.y + 1, pos.z))
+                .and_then(|block| BlockPhysics::get(*block).stuck_multiplier);
+        }
+
+        state.jump();
+
+        // Only go up if the jump is high enough for it.
+        let mut apex = state;
+        while apex.vel.y > 0. {
+            apex.tick();
+        }
+        let max_y = (apex.pos.y - 1.2).floor() as i32;
+        let target_y = params.direction.get_y_offset().min(max_y);
+
+        state.tick_until_landed((target_y + 1) as f64);
+
+        // Any gap up to the furthest one is reachable, since the player can
+        // always jump shorter. There has to be at least a one block gap.
+        let furthest_z = state.get_block_pos().z;
+        if furthest_z < takeoff_z + 2 {
+            return None;
+        }
+        let landing_z = rng.gen_range(takeoff_z + 2..=furthest_z);
+
+        let landing = BlockPos::new(0, target_y, landing_z);
+        let end = BlockPos::new(0, target_y, landing_z + 1);
+
+        blocks.extend(floor);
+        blocks.extend(inside);
+        blocks.insert(landing, block);
+        blocks.insert(end, block);
+
+        Some(PredictionState::running_jump_block(
+            self.start + end,
+            random_yaw(),
+        ))
+    }
+}
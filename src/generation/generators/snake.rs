@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use valence::prelude::*;
+
+use crate::{
+    generation::{
+        generation::Snake,
+        generator::{BlockGenParams, Generator},
+    },
+    prediction::prediction_state::PredictionState,
+    utils::*,
+};
+
+/// The `SnakeGenParams` struct represents the parameters used to generate a
+/// snake.
+///
+/// Properties:
+///
+/// * `block`: The `block` property is the name of the block the snake and the
+/// platforms at either end of it are made of.
+/// * `length`: The `length` property is how many blocks long the snake is.
+/// * `delay`: The `delay` property is the amount of ticks between each move of
+/// the snake.
+#[derive(Clone, Debug)]
+pub struct SnakeGenParams {
+    pub block: String,
+    pub length: usize,
+    pub delay: usize,
+}
+
+impl Generator {
+    /// Generates a snake that crawls along a winding path between two platforms.
+    /// The path is too long to jump over, so the player has to ride the snake.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the delay of the snake is 0.
+    pub fn generate_snake(
+        &self,
+        snake: &SnakeGenParams,
+        params: &BlockGenParams,
+        blocks: &mut HashMap<BlockPos, BlockState>,
+        snakes: &mut Vec<Snake>,
+    ) -> PredictionState {
+        assert!(snake.delay > 0, "The delay of a snake can't be 0");
+
+        let mut rng = rand::thread_rng();
+
+        let steps = rng.gen_range(6..=10);
+
+        let mut path = vec![BlockPos::new(0, 0, 1)];
+        let mut side = 0;
+
+        for _ in 1..steps {
+            let last = *path.last().unwrap();
+
+            // Go sideways sometimes, but never straight back to where the snake
+            // just came from.
+            let dx = match rng.gen_range(0..5) {
+                0 if last.x < 2 && side != -1 => 1,
+                1 if last.x > -2 && side != 1 => -1,
+                _ => 0,
+            };
+            side = dx;
+
+            path.push(if dx == 0 {
+                BlockPos::new(last.x, last.y, last.z + 1)
+            } else {
+                BlockPos::new(last.x + dx, last.y, last.z)
+            });
+        }
+
+        // Going sideways doesn't get the snake any further, so it keeps going
+        // forward until the end is too far away to jump to.
+        let min_end_z = get_max_jump_z() + 1;
+        while path.last().unwrap().z + 1 < min_end_z {
+            let last = *path.last().unwrap();
+            path.push(BlockPos::new(last.x, last.y, last.z + 1));
+        }
+
+        let last = *path.last().unwrap();
+        let end = BlockPos::new(last.x, last.y, last.z + 1);
+
+        blocks.insert(
+            BlockPos::new(0, 0, 0),
+            params.block_map.get_block(&snake.block),
+        );
+        blocks.insert(end, params.block_map.get_block(&snake.block));
+
+        snakes.push(Snake {
+            phase: rng.gen_range(0..path.len() + snake.length),
+            blocks: path,
+            block: params.block_map.get_block(&snake.block),
+            length: snake.length,
+            delay: snake.delay,
+        });
+
+        PredictionState::running_jump_block(self.start + end, random_yaw())
+    }
+}
+
+/// Gets the z position of the furthest block the player can land on with a
+/// straight running jump off of the block at 0, 0, 0. The player jumps with the
+/// edge of their hitbox over the edge of the block, and lands with the edge of
+/// their hitbox over the landing.
+fn get_max_jump_z() -> i32 {
+    let mut state =
+        PredictionState::running_jump_vec(DVec3::new(0.5, 1., 1. + PLAYER_WIDTH / 2.), 0.);
+    state.tick_until_landed(1.);
+
+    (state.pos.z + PLAYER_WIDTH / 2.).floor() as i32
+}
@@ -1,4 +1,9 @@
 pub mod block_collection;
+pub mod custom_generation;
+pub mod difficulty;
 pub mod generation;
 pub mod generator;
+pub mod generators;
+pub mod presets;
 pub mod theme;
+pub mod validator;
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use valence::{math::IVec3, prelude::*};
+
+use super::custom_generation::{
+    ComplexCustomPreset, ComplexCustomTile, MultiCustomPreset, SingleCustomPreset,
+};
+
+lazy_static! {
+    /// The hand-built segments used by the `SingleCustom` generation type. They
+    /// are all built going south (+z), starting at the origin.
+    pub static ref SINGLE_CUSTOM_PRESETS: Vec<SingleCustomPreset> = vec![
+        SingleCustomPreset {
+            name: "zigzag".to_owned(),
+            blocks: HashMap::from([
+                (BlockPos::new(0, 0, 0), BlockState::SMOOTH_STONE),
+                (BlockPos::new(2, 0, 2), BlockState::SMOOTH_STONE),
+                (BlockPos::new(0, 0, 4), BlockState::SMOOTH_STONE),
+                (BlockPos::new(0, 0, 5), BlockState::SMOOTH_STONE),
+            ]),
+            start_pos: BlockPos::new(0, 0, 0),
+            end_pos: BlockPos::new(0, 0, 5),
+        },
+        SingleCustomPreset {
+            name: "stair_hop".to_owned(),
+            blocks: HashMap::from([
+                (BlockPos::new(0, 0, 0), BlockState::STONE_BRICKS),
+                (
+                    BlockPos::new(0, 1, 2),
+                    BlockState::STONE_BRICK_STAIRS.set(PropName::Facing, PropValue::South),
+                ),
+                (BlockPos::new(0, 2, 4), BlockState::STONE_BRICKS),
+            ]),
+            start_pos: BlockPos::new(0, 0, 0),
+            end_pos: BlockPos::new(0, 2, 4),
+        },
+        SingleCustomPreset {
+            name: "slab_hop".to_owned(),
+            blocks: HashMap::from([
+                (BlockPos::new(0, 0, 0), BlockState::OAK_PLANKS),
+                (BlockPos::new(1, 0, 2), BlockState::OAK_SLAB),
+                (BlockPos::new(-1, 0, 4), BlockState::OAK_SLAB),
+                (BlockPos::new(-1, 0, 6), BlockState::OAK_PLANKS),
+            ]),
+            start_pos: BlockPos::new(0, 0, 0),
+            end_pos: BlockPos::new(-1, 0, 6),
+        },
+    ];
+
+    /// The hand-built sequences used by the `MultiCustom` generation type. Every
+    /// piece is built going south (+z), starting at the origin. The end position
+    /// of a piece is where the start position of the next piece goes.
+    pub static ref MULTI_CUSTOM_PRESETS: Vec<MultiCustomPreset> = vec![
+        MultiCustomPreset {
+            name: "bridge".to_owned(),
+            start: SingleCustomPreset {
+                name: "bridge_start".to_owned(),
+                blocks: HashMap::from([
+                    (BlockPos::new(0, 0, 0), BlockState::OAK_PLANKS),
+                    (
+                        BlockPos::new(0, 1, 1),
+                        BlockState::OAK_STAIRS.set(PropName::Facing, PropValue::South),
+                    ),
+                ]),
+                start_pos: BlockPos::new(0, 0, 0),
+                end_pos: BlockPos::new(0, 1, 2),
+            },
+            middles: vec![
+                SingleCustomPreset {
+                    name: "bridge_plank".to_owned(),
+                    blocks: HashMap::from([
+                        (BlockPos::new(0, 0, 0), BlockState::OAK_PLANKS),
+                        (BlockPos::new(0, 0, 1), BlockState::OAK_PLANKS),
+                        (BlockPos::new(-1, 1, 0), BlockState::OAK_FENCE),
+                        (BlockPos::new(1, 1, 0), BlockState::OAK_FENCE),
+                    ]),
+                    start_pos: BlockPos::new(0, 0, 0),
+                    end_pos: BlockPos::new(0, 0, 3),
+                },
+                SingleCustomPreset {
+                    name: "bridge_slab".to_owned(),
+                    blocks: HashMap::from([
+                        (BlockPos::new(0, 0, 0), BlockState::OAK_PLANKS),
+                        (BlockPos::new(0, 0, 2), BlockState::OAK_SLAB),
+                    ]),
+                    start_pos: BlockPos::new(0, 0, 0),
+                    end_pos: BlockPos::new(0, 0, 4),
+                },
+            ],
+            end: SingleCustomPreset {
+                name: "bridge_end".to_owned(),
+                blocks: HashMap::from([
+                    (BlockPos::new(0, 0, 0), BlockState::OAK_PLANKS),
+                    (
+                        BlockPos::new(0, 0, 1),
+                        BlockState::OAK_STAIRS.set(PropName::Facing, PropValue::North),
+                    ),
+                    (BlockPos::new(0, -1, 2), BlockState::OAK_PLANKS),
+                ]),
+                start_pos: BlockPos::new(0, 0, 0),
+                end_pos: BlockPos::new(0, -1, 2),
+            },
+            min_middles: 2,
+            max_middles: 5,
+        },
+        MultiCustomPreset {
+            name: "tower_climb".to_owned(),
+            start: SingleCustomPreset {
+                name: "tower_climb_start".to_owned(),
+                blocks: HashMap::from([(BlockPos::new(0, 0, 0), BlockState::STONE_BRICKS)]),
+                start_pos: BlockPos::new(0, 0, 0),
+                end_pos: BlockPos::new(0, 1, 2),
+            },
+            middles: vec![
+                SingleCustomPreset {
+                    name: "tower_climb_left".to_owned(),
+                    blocks: HashMap::from([
+                        (BlockPos::new(0, 0, 0), BlockState::STONE_BRICKS),
+                        (BlockPos::new(0, -1, 0), BlockState::STONE_BRICKS),
+                    ]),
+                    start_pos: BlockPos::new(0, 0, 0),
+                    end_pos: BlockPos::new(-1, 1, 2),
+                },
+                SingleCustomPreset {
+                    name: "tower_climb_right".to_owned(),
+                    blocks: HashMap::from([
+                        (BlockPos::new(0, 0, 0), BlockState::STONE_BRICKS),
+                        (BlockPos::new(0, -1, 0), BlockState::STONE_BRICKS),
+                    ]),
+                    start_pos: BlockPos::new(0, 0, 0),
+                    end_pos: BlockPos::new(1, 1, 2),
+                },
+            ],
+            end: SingleCustomPreset {
+                name: "tower_climb_end".to_owned(),
+                blocks: HashMap::from([
+                    (BlockPos::new(0, 0, 0), BlockState::CHISELED_STONE_BRICKS),
+                    (BlockPos::new(0, 0, 1), BlockState::CHISELED_STONE_BRICKS),
+                ]),
+                start_pos: BlockPos::new(0, 0, 0),
+                end_pos: BlockPos::new(0, 0, 1),
+            },
+            min_middles: 1,
+            max_middles: 4,
+        },
+    ];
+
+    /// The tile-based segments used by the `ComplexCustom` generation type.
+    pub static ref COMPLEX_CUSTOM_PRESETS: Vec<ComplexCustomPreset> = vec![
+        ComplexCustomPreset {
+            name: "maze".to_owned(),
+            tiles: vec![
+                ComplexCustomTile {
+                    name: "dead_end".to_owned(),
+                    blocks: HashMap::from([
+                        (BlockPos::new(0, 0, -2), BlockState::STONE_BRICKS),
+                        (BlockPos::new(0, 1, 0), BlockState::MOSSY_STONE_BRICKS),
+                    ]),
+                    sockets: HashMap::from([
+                        (BlockPos::new(0, 0, -1), "path".to_owned()),
+                    ]),
+                },
+                ComplexCustomTile {
+                    name: "straight".to_owned(),
+                    blocks: HashMap::from([
+                        (BlockPos::new(0, 0, -2), BlockState::STONE_BRICKS),
+                        (BlockPos::new(0, 0, 0), BlockState::MOSSY_STONE_BRICKS),
+                        (BlockPos::new(0, 0, 2), BlockState::STONE_BRICKS),
+                    ]),
+                    sockets: HashMap::from([
+                        (BlockPos::new(0, 0, -1), "path".to_owned()),
+                        (BlockPos::new(0, 0, 1), "path".to_owned()),
+                    ]),
+                },
+                ComplexCustomTile {
+                    name: "corner".to_owned(),
+                    blocks: HashMap::from([
+                        (BlockPos::new(0, 0, -2), BlockState::STONE_BRICKS),
+                        (BlockPos::new(1, 1, -1), BlockState::MOSSY_STONE_BRICKS),
+                        (BlockPos::new(2, 0, 0), BlockState::STONE_BRICKS),
+                    ]),
+                    sockets: HashMap::from([
+                        (BlockPos::new(0, 0, -1), "path".to_owned()),
+                        (BlockPos::new(1, 0, 0), "path".to_owned()),
+                    ]),
+                },
+                ComplexCustomTile {
+                    name: "junction".to_owned(),
+                    blocks: HashMap::from([
+                        (BlockPos::new(0, 0, -2), BlockState::STONE_BRICKS),
+                        (BlockPos::new(0, 0, 0), BlockState::MOSSY_STONE_BRICKS),
+                        (BlockPos::new(2, 0, 0), BlockState::STONE_BRICKS),
+                        (BlockPos::new(-2, 0, 0), BlockState::STONE_BRICKS),
+                    ]),
+                    sockets: HashMap::from([
+                        (BlockPos::new(0, 0, -1), "path".to_owned()),
+                        (BlockPos::new(1, 0, 0), "path".to_owned()),
+                        (BlockPos::new(-1, 0, 0), "path".to_owned()),
+                    ]),
+                },
+                ComplexCustomTile {
+                    name: "crossing".to_owned(),
+                    blocks: HashMap::from([
+                        (BlockPos::new(0, 0, -2), BlockState::STONE_BRICKS),
+                        (BlockPos::new(0, 1, 0), BlockState::MOSSY_STONE_BRICKS),
+                        (BlockPos::new(2, 0, 0), BlockState::STONE_BRICKS),
+                        (BlockPos::new(-2, 0, 0), BlockState::STONE_BRICKS),
+                        (BlockPos::new(0, 0, 2), BlockState::STONE_BRICKS),
+                    ]),
+                    sockets: HashMap::from([
+                        (BlockPos::new(0, 0, -1), "path".to_owned()),
+                        (BlockPos::new(1, 0, 0), "path".to_owned()),
+                        (BlockPos::new(-1, 0, 0), "path".to_owned()),
+                        (BlockPos::new(0, 0, 1), "path".to_owned()),
+                    ]),
+                },
+            ],
+            tile_size: 5,
+            size: IVec3::new(3, 1, 3),
+        },
+    ];
+}
@@ -20,11 +20,21 @@ pub struct GenerationTheme {
 }
 
 impl GenerationTheme {
+    /// # Panics
+    ///
+    /// Panics if there are no generation types, or if the first one can fail to
+    /// generate. The generator falls back to the first generation type when
+    /// nothing else works, so it has to work everywhere.
     pub fn new(
         name: String,
         block_map: BlockCollectionMap,
         generation_types: WeightedVec<GenerationType>,
     ) -> Self {
+        assert!(
+            !generation_types[0].can_fail(),
+            "The first generation type of a theme can't be one that can fail"
+        );
+
         Self {
             name,
             block_map,
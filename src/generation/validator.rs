@@ -0,0 +1,235 @@
+use std::{collections::HashMap, fmt};
+
+use valence::prelude::*;
+
+use crate::prediction::{
+    collision_shapes::{get_block_top, get_collision_shape},
+    prediction_state::PredictionState,
+    simulator::{simulate_jump, JumpOutcome},
+};
+
+use super::generation::Generation;
+
+/// The `Rejection` enum represents the reason a generation was rejected by
+/// `validate`, or couldn't be generated at all.
+///
+/// Variants:
+///
+/// * `Failed`: The `Failed` variant means the generator couldn't generate
+/// anything at the start.
+/// * `NoLanding`: The `NoLanding` variant means there is nothing to stand on at
+/// the start of the generation.
+/// * `Unreachable`: The `Unreachable` variant means the jump from the end of the
+/// previous generation doesn't land on the start of the generation, even without
+/// any of the other blocks of the generation. It has the outcome of the simulated
+/// jump.
+/// * `Blocked`: The `Blocked` variant means a block of the generation gets in the
+/// way of the jump into it. It has the outcome of the simulated jump.
+#[derive(Clone, Copy, Debug)]
+pub enum Rejection {
+    Failed,
+    NoLanding,
+    Unreachable(JumpOutcome),
+    Blocked(JumpOutcome),
+}
+
+impl Rejection {
+    /// Returns true if generating again at the same start can get rid of the
+    /// rejection. A jump that doesn't land on the start stays that way, whatever
+    /// is generated there.
+    pub fn can_retry(&self) -> bool {
+        !matches!(self, Rejection::Unreachable(_))
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::Failed => write!(f, "nothing could be generated"),
+            Rejection::NoLanding => write!(f, "there is nothing to land on"),
+            Rejection::Unreachable(outcome) => {
+                write!(f, "the jump doesn't reach the landing ({outcome:?})")
+            }
+            Rejection::Blocked(JumpOutcome::HitWall(pos)) => {
+                write!(f, "the jump hits a wall at {pos}")
+            }
+            Rejection::Blocked(JumpOutcome::HitCeiling(pos)) => {
+                write!(f, "the jump hits a ceiling at {pos}")
+            }
+            Rejection::Blocked(JumpOutcome::LandedElsewhere(pos)) => {
+                write!(f, "the jump lands somewhere else, at {pos}")
+            }
+            Rejection::Blocked(outcome) => write!(f, "the jump doesn't land ({outcome:?})"),
+        }
+    }
+}
+
+/// Checks that the player can get from the end of `previous` to the start of
+/// `generation` with `jump`. The jump has to land on the start with only the
+/// blocks of `previous` around, and the blocks of `generation` can't get in its
+/// way either.
+pub fn validate(
+    previous: &Generation,
+    jump: PredictionState,
+    generation: &Generation,
+) -> Result<(), Rejection> {
+    let landing = generation.offset;
+    let landing_block = get_landing_block(generation).ok_or(Rejection::NoLanding)?;
+    let target_y = landing.y as f64 + get_block_top(landing_block);
+
+    let mut world = HashMap::new();
+    add_solid_blocks(&mut world, previous);
+    world.insert(landing, landing_block);
+
+    match simulate_jump(jump, target_y, &world) {
+        JumpOutcome::Landed => {}
+        outcome => return Err(Rejection::Unreachable(outcome)),
+    }
+
+    add_solid_blocks(&mut world, generation);
+
+    match simulate_jump(jump, target_y, &world) {
+        JumpOutcome::Landed => Ok(()),
+        outcome => Err(Rejection::Blocked(outcome)),
+    }
+}
+
+/// Gets the block at the start of the generation, if the player can stand on it.
+pub fn get_landing_block(generation: &Generation) -> Option<BlockState> {
+    let start = BlockPos::new(0, 0, 0);
+
+    let block = generation.blocks.get(&start).copied().or_else(|| {
+        generation
+            .alt_blocks
+            .get(&start)
+            .map(|alt_block| alt_block.get_block())
+    })?;
+
+    if get_collision_shape(block).is_empty() {
+        None
+    } else {
+        Some(block)
+    }
+}
+
+/// Adds every block of the generation the player could bump into to `world`, at
+/// its absolute position. Blink blocks are added as if they are on, and snakes
+/// are left out, since they move.
+fn add_solid_blocks(world: &mut HashMap<BlockPos, BlockState>, generation: &Generation) {
+    let offset = generation.offset;
+
+    for (pos, block) in &generation.blocks {
+        world.insert(*pos + offset, *block);
+    }
+
+    for (pos, alt_block) in &generation.alt_blocks {
+        world.insert(*pos + offset, alt_block.get_block());
+    }
+
+    for child in &generation.children {
+        for (pos, block) in &child.blocks {
+            world.insert(*pos + offset, *block);
+        }
+    }
+
+    for blink_block in &generation.blink_blocks {
+        world.insert(blink_block.pos + offset, blink_block.on_block);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Gets a generation at `offset` that is made of `blocks`.
+    fn get_generation(offset: BlockPos, blocks: &[(BlockPos, BlockState)]) -> Generation {
+        Generation {
+            blocks: blocks.iter().copied().collect(),
+            children: Vec::new(),
+            alt_blocks: HashMap::new(),
+            ordered: true,
+            offset,
+            end_state: PredictionState::running_jump_block(offset, 0.),
+            snakes: Vec::new(),
+            blink_blocks: Vec::new(),
+            fluids: HashMap::new(),
+            launch_pads: HashMap::new(),
+            platforms: Vec::new(),
+            reached_tick: None,
+            difficulty: 0.,
+        }
+    }
+
+    /// Gets a single stone block at 0, 64, 0 to jump off of, and the position the
+    /// jump off of it is predicted to land at, the same way the generator does.
+    fn get_previous() -> (Generation, BlockPos) {
+        let previous = get_generation(
+            BlockPos::new(0, 64, 0),
+            &[(BlockPos::new(0, 0, 0), BlockState::STONE)],
+        );
+
+        let mut state = previous.end_state;
+        state.tick_until_landed(65.);
+
+        (previous, state.get_block_pos())
+    }
+
+    #[test]
+    fn accepts_predicted_landing() {
+        let (previous, landing) = get_previous();
+        let generation = get_generation(landing, &[(BlockPos::new(0, 0, 0), BlockState::STONE)]);
+
+        assert!(validate(&previous, previous.end_state, &generation).is_ok());
+    }
+
+    #[test]
+    fn rejects_missing_landing() {
+        let (previous, landing) = get_previous();
+        let generation = get_generation(landing, &[(BlockPos::new(0, 0, 1), BlockState::STONE)]);
+
+        assert!(matches!(
+            validate(&previous, previous.end_state, &generation),
+            Err(Rejection::NoLanding)
+        ));
+    }
+
+    #[test]
+    fn rejects_landing_out_of_reach() {
+        let (previous, _) = get_previous();
+        let generation = get_generation(
+            BlockPos::new(0, 64, 10),
+            &[(BlockPos::new(0, 0, 0), BlockState::STONE)],
+        );
+
+        let rejection = validate(&previous, previous.end_state, &generation).unwrap_err();
+
+        assert!(matches!(
+            rejection,
+            Rejection::Unreachable(JumpOutcome::Missed)
+        ));
+        assert!(!rejection.can_retry());
+    }
+
+    #[test]
+    fn rejects_wall_in_the_way() {
+        let (previous, landing) = get_previous();
+
+        // The wall is between the block the player jumps off of and the landing.
+        let wall_z = 2 - landing.z;
+        let mut blocks = vec![(BlockPos::new(0, 0, 0), BlockState::STONE)];
+        for x in -2..=2 {
+            for y in 1..=3 {
+                blocks.push((BlockPos::new(x, y, wall_z), BlockState::STONE));
+            }
+        }
+        let generation = get_generation(landing, &blocks);
+
+        let rejection = validate(&previous, previous.end_state, &generation).unwrap_err();
+
+        assert!(matches!(
+            rejection,
+            Rejection::Blocked(JumpOutcome::HitWall(_))
+        ));
+        assert!(rejection.can_retry());
+    }
+}
@@ -6,10 +6,20 @@ use game_state::GameState;
 use generation::block_collection::*;
 
 use generation::generator::{GenerationType, Generator};
-
+use generation::generators::{
+    blink_blocks::BlinkBlocksGenParams, branch::BranchGenParams, bridge::BridgeGenParams,
+    cave::CaveGenParams, climb::ClimbGenParams, crumble::CrumbleGenParams, door::DoorGenParams,
+    elevator::ElevatorGenParams, head_hit::HeadHitGenParams, ice::IceGenParams,
+    indoor::IndoorGenParams, island::IslandGenParams, launch_pad::LaunchPadGenParams,
+    neo::NeoGenParams, partial::PartialGenParams, platform::PlatformGenParams,
+    slime::SlimeGenParams, slowdown::SlowdownGenParams, snake::SnakeGenParams,
+};
+
+use generation::presets::{COMPLEX_CUSTOM_PRESETS, MULTI_CUSTOM_PRESETS, SINGLE_CUSTOM_PRESETS};
 use generation::theme::GenerationTheme;
 use prediction::prediction_state::PredictionState;
 use utils::JumpDirection;
+use valence::entity::EntityId;
 use valence::prelude::*;
 use valence::protocol::sound::{Sound, SoundCategory};
 use valence::spawn::IsFlat;
@@ -73,7 +83,7 @@ fn init_clients(
         ),
         Added<Client>,
     >,
-    layers: Query<Entity, (With<ChunkLayer>, With<EntityLayer>)>,
+    layers: Query<Entity, (With<ChunkLayer>, With<EntityLayer>, Without<Client>)>,
     server: Res<Server>,
     dimensions: Res<DimensionTypeRegistry>,
     biomes: Res<BiomeRegistry>,
@@ -94,6 +104,7 @@ fn init_clients(
         layer_id.0 = layer;
         visible_chunk_layer.0 = layer;
         visible_entity_layers.0.insert(layer);
+        visible_entity_layers.0.insert(entity);
 
         visible_chunk_layer.0 = entity;
         is_flat.0 = true;
@@ -101,41 +112,350 @@ fn init_clients(
 
         client.send_chat_message("Welcome to epic infinite parkour game!".italic());
 
-        let state = GameState {
+        let mut state = GameState {
             generations: VecDeque::new(),
             direction: JumpDirection::DoesntMatter,
             theme: GenerationTheme::new(
                 "name".to_owned(),
-                BlockCollectionMap::from([(
-                    "concrete",
-                    BlockCollection(BlockChoice {
-                        blocks: weighted_vec![
-                            BlockState::WHITE_CONCRETE,
-                            BlockState::ORANGE_CONCRETE,
-                            BlockState::MAGENTA_CONCRETE,
-                            BlockState::LIGHT_BLUE_CONCRETE,
-                            BlockState::YELLOW_CONCRETE,
-                            BlockState::LIME_CONCRETE,
-                            BlockState::PINK_CONCRETE,
-                            BlockState::GRAY_CONCRETE,
-                            BlockState::LIGHT_GRAY_CONCRETE,
-                            BlockState::CYAN_CONCRETE,
-                            BlockState::PURPLE_CONCRETE,
-                            BlockState::BLUE_CONCRETE,
-                            BlockState::BROWN_CONCRETE,
-                            BlockState::GREEN_CONCRETE,
-                            BlockState::RED_CONCRETE,
-                            // BlockState::BLACK_CONCRETE, // black has no contrast and is completely invisible at night
-                        ],
-                        uniform: true,
-                    }),
-                )]),
-                weighted_vec![(GenerationType::Single("concrete".to_string()), 100.0),],
+                BlockCollectionMap::from([
+                    (
+                        "concrete",
+                        BlockCollection(BlockChoice {
+                            blocks: weighted_vec![
+                                BlockState::WHITE_CONCRETE,
+                                BlockState::ORANGE_CONCRETE,
+                                BlockState::MAGENTA_CONCRETE,
+                                BlockState::LIGHT_BLUE_CONCRETE,
+                                BlockState::YELLOW_CONCRETE,
+                                BlockState::LIME_CONCRETE,
+                                BlockState::PINK_CONCRETE,
+                                BlockState::GRAY_CONCRETE,
+                                BlockState::LIGHT_GRAY_CONCRETE,
+                                BlockState::CYAN_CONCRETE,
+                                BlockState::PURPLE_CONCRETE,
+                                BlockState::BLUE_CONCRETE,
+                                BlockState::BROWN_CONCRETE,
+                                BlockState::GREEN_CONCRETE,
+                                BlockState::RED_CONCRETE,
+                                // BlockState::BLACK_CONCRETE, // black has no contrast and is completely invisible at night
+                            ],
+                            uniform: true,
+                        }),
+                    ),
+                    (
+                        "grass",
+                        BlockCollection(BlockChoice {
+                            blocks: weighted_vec![BlockState::GRASS_BLOCK],
+                            uniform: true,
+                        }),
+                    ),
+                    (
+                        "dirt",
+                        BlockCollection(BlockChoice {
+                            blocks: weighted_vec![
+                                (BlockState::DIRT, 3.0),
+                                (BlockState::COARSE_DIRT, 1.0),
+                            ],
+                            uniform: false,
+                        }),
+                    ),
+                    (
+                        "stone",
+                        BlockCollection(BlockChoice {
+                            blocks: weighted_vec![
+                                (BlockState::STONE, 3.0),
+                                (BlockState::ANDESITE, 1.0),
+                                (BlockState::COBBLESTONE, 1.0),
+                            ],
+                            uniform: false,
+                        }),
+                    ),
+                    (
+                        "planks",
+                        BlockCollection(BlockChoice {
+                            blocks: weighted_vec![
+                                BlockState::OAK_PLANKS,
+                                BlockState::SPRUCE_PLANKS,
+                                BlockState::BIRCH_PLANKS,
+                                BlockState::DARK_OAK_PLANKS,
+                            ],
+                            uniform: true,
+                        }),
+                    ),
+                    (
+                        "stone_brick_full",
+                        BlockCollection(BlockChoice {
+                            blocks: weighted_vec![BlockState::STONE_BRICKS],
+                            uniform: true,
+                        }),
+                    ),
+                    (
+                        "stone_brick_slab",
+                        BlockCollection(BlockChoice {
+                            blocks: weighted_vec![BlockState::STONE_BRICK_SLAB],
+                            uniform: true,
+                        }),
+                    ),
+                    (
+                        "stone_brick_stair",
+                        BlockCollection(BlockChoice {
+                            blocks: weighted_vec![BlockState::STONE_BRICK_STAIRS],
+                            uniform: true,
+                        }),
+                    ),
+                    (
+                        "climbable",
+                        BlockCollection(BlockChoice {
+                            blocks: weighted_vec![BlockState::LADDER, BlockState::VINE],
+                            uniform: true,
+                        }),
+                    ),
+                    (
+                        "ice",
+                        BlockCollection(BlockChoice {
+                            blocks: weighted_vec![
+                                (BlockState::PACKED_ICE, 3.0),
+                                (BlockState::BLUE_ICE, 1.0),
+                            ],
+                            uniform: false,
+                        }),
+                    ),
+                    (
+                        "slowdown",
+                        BlockCollection(BlockChoice {
+                            blocks: weighted_vec![
+                                BlockState::SOUL_SAND,
+                                BlockState::HONEY_BLOCK,
+                                BlockState::COBWEB,
+                            ],
+                            uniform: true,
+                        }),
+                    ),
+                    (
+                        "partial",
+                        BlockCollection(BlockChoice {
+                            blocks: weighted_vec![
+                                BlockState::OAK_TRAPDOOR,
+                                BlockState::OAK_FENCE,
+                                BlockState::COBBLESTONE_WALL,
+                                BlockState::STONE_SLAB,
+                                BlockState::SKELETON_SKULL,
+                                BlockState::GLASS_PANE,
+                                BlockState::IRON_BARS,
+                            ],
+                            uniform: false,
+                        }),
+                    ),
+                    (
+                        "crumble",
+                        BlockCollection(BlockChoice {
+                            blocks: weighted_vec![
+                                BlockState::CRACKED_STONE_BRICKS,
+                                BlockState::CRACKED_DEEPSLATE_BRICKS,
+                            ],
+                            uniform: true,
+                        }),
+                    ),
+                    (
+                        "launch_pad",
+                        BlockCollection(BlockChoice {
+                            blocks: weighted_vec![BlockState::EMERALD_BLOCK],
+                            uniform: true,
+                        }),
+                    ),
+                    (
+                        "button",
+                        BlockCollection(BlockChoice {
+                            blocks: weighted_vec![BlockState::REDSTONE_BLOCK],
+                            uniform: true,
+                        }),
+                    ),
+                    (
+                        "slime",
+                        BlockCollection(BlockChoice {
+                            blocks: weighted_vec![BlockState::SLIME_BLOCK],
+                            uniform: true,
+                        }),
+                    ),
+                ]),
+                weighted_vec![
+                    (GenerationType::Single("concrete".to_string()), 100.0),
+                    (GenerationType::Ramp("stone_brick".to_string()), 20.0),
+                    (
+                        GenerationType::Slime(SlimeGenParams {
+                            block: "concrete".to_string(),
+                            slime: "slime".to_string(),
+                            min_drop: 3,
+                            max_drop: 5,
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::Island(IslandGenParams {
+                            top: "grass".to_string(),
+                            filler: "dirt".to_string(),
+                            bottom: "stone".to_string(),
+                            min_radius: 2,
+                            max_radius: 4,
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::Indoor(IndoorGenParams {
+                            floor: "planks".to_string(),
+                            wall: "stone_brick_full".to_string(),
+                            ceiling: "stone_brick_full".to_string(),
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::Climb(ClimbGenParams {
+                            block: "concrete".to_string(),
+                            wall: "planks".to_string(),
+                            climbable: "climbable".to_string(),
+                            min_height: 3,
+                            max_height: 6,
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::Ice(IceGenParams {
+                            ice: "ice".to_string(),
+                            block: "concrete".to_string(),
+                            min_length: 4,
+                            max_length: 8,
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::Slowdown(SlowdownGenParams {
+                            block: "concrete".to_string(),
+                            surface: "slowdown".to_string(),
+                            min_length: 2,
+                            max_length: 4,
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::Elevator(ElevatorGenParams {
+                            block: "concrete".to_string(),
+                            min_height: 3,
+                            max_height: 6,
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::Partial(PartialGenParams {
+                            block: "concrete".to_string(),
+                            partial: "partial".to_string(),
+                            min_jumps: 2,
+                            max_jumps: 4,
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::Branch(BranchGenParams {
+                            block: "concrete".to_string(),
+                            min_branches: 2,
+                            max_branches: 3,
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::Bridge(BridgeGenParams {
+                            block: "concrete".to_string(),
+                            bridge: "planks".to_string(),
+                            min_length: 5,
+                            max_length: 8,
+                            delay: 10,
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::Crumble(CrumbleGenParams {
+                            block: "concrete".to_string(),
+                            crumble: "crumble".to_string(),
+                            min_jumps: 2,
+                            max_jumps: 4,
+                            delay: 10,
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::Door(DoorGenParams {
+                            block: "concrete".to_string(),
+                            wall: "stone_brick_full".to_string(),
+                            door: "planks".to_string(),
+                            button: "button".to_string(),
+                            combo: 10,
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::Platform(PlatformGenParams {
+                            block: "concrete".to_string(),
+                            platform: "planks".to_string(),
+                            min_distance: 3,
+                            max_distance: 6,
+                            ticks_per_block: 10,
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::LaunchPad(LaunchPadGenParams {
+                            block: "concrete".to_string(),
+                            pad: "launch_pad".to_string(),
+                            min_velocity: 0.8,
+                            max_velocity: 1.2,
+                            speed: 0.6,
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::Neo(NeoGenParams {
+                            block: "concrete".to_string(),
+                            wall: "stone_brick_full".to_string(),
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::Cave(CaveGenParams {
+                            block: "stone".to_string(),
+                            platform: "stone_brick_full".to_string(),
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::Snake(SnakeGenParams {
+                            block: "concrete".to_string(),
+                            length: 3,
+                            delay: 5,
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::BlinkBlocks(BlinkBlocksGenParams {
+                            block: "concrete".to_string(),
+                            ghost: None,
+                            on_ticks: 30,
+                            off_ticks: 20,
+                        }),
+                        10.0,
+                    ),
+                    (
+                        GenerationType::HeadHit(HeadHitGenParams {
+                            block: "concrete".to_string(),
+                            ceiling: "stone_brick_full".to_string(),
+                        }),
+                        10.0,
+                    ),
+                ],
             ),
             score: 0,
             combo: 0,
             target_y: 0,
             stopped_running: false,
+            launched: false,
             tick: 0,
             prev_pos: DVec3::new(
                 START_POS.x as f64 + 0.5,
@@ -153,22 +473,41 @@ fn init_clients(
             ),
         };
 
+        state.theme.generation_types.push_all(
+            SINGLE_CUSTOM_PRESETS
+                .iter()
+                .map(|preset| (GenerationType::SingleCustom(preset.clone()), 5.0)),
+        );
+        state.theme.generation_types.push_all(
+            MULTI_CUSTOM_PRESETS
+                .iter()
+                .map(|preset| (GenerationType::MultiCustom(preset.clone()), 5.0)),
+        );
+        state.theme.generation_types.push_all(
+            COMPLEX_CUSTOM_PRESETS
+                .iter()
+                .map(|preset| (GenerationType::ComplexCustom(preset.clone()), 2.0)),
+        );
+
         let layer = ChunkLayer::new(ident!("overworld"), &dimensions, &biomes, &server);
+        let entity_layer = EntityLayer::new(&server);
 
-        commands.entity(entity).insert((state, layer));
+        commands.entity(entity).insert((state, layer, entity_layer));
     }
 }
 
 fn reset_clients(
     mut clients: Query<(
+        Entity,
         &mut Client,
         &mut Position,
         &mut Look,
         &mut GameState,
         &mut ChunkLayer,
     )>,
+    mut commands: Commands,
 ) {
-    for (mut client, mut pos, mut look, mut state, mut layer) in clients.iter_mut() {
+    for (entity, mut client, mut pos, mut look, mut state, mut layer) in clients.iter_mut() {
         state.test_state.yaw = look.yaw / 180.0 * std::f32::consts::PI;
         state.test_state.vel = pos.0 - state.prev_pos;
 
@@ -200,17 +539,18 @@ fn reset_clients(
             {
                 let state = &mut *state;
                 for block in &state.generations {
-                    block.remove(&mut layer);
+                    block.remove(&mut layer, &mut commands);
                 }
             }
 
             state.generations.clear();
-            let gen = Generator::first_in_generation(START_POS, &state.theme);
-            gen.place(&mut layer);
+            let mut gen = Generator::first_in_generation(START_POS, &state.theme);
+            gen.reached_tick = Some(state.tick);
+            gen.place(&mut layer, &mut commands, entity, state.tick);
             state.generations.push_back(gen);
 
             for _ in 0..10 {
-                generate_next_block(&mut state, &mut layer);
+                generate_next_block(&mut state, &mut layer, &mut commands, entity);
             }
 
             pos.set([
@@ -227,9 +567,14 @@ fn reset_clients(
 fn cleanup_clients(
     mut disconnected_clients: RemovedComponents<Client>,
     mut query: Query<&mut GameState>,
+    mut commands: Commands,
 ) {
     for entity in disconnected_clients.iter() {
         if let Ok(mut state) = query.get_mut(entity) {
+            for generation in &state.generations {
+                generation.despawn(&mut commands);
+            }
+
             state.generations.clear();
         }
     }
@@ -245,8 +590,53 @@ fn detect_stop_running(mut event: EventReader<SprintEvent>, mut clients: Query<&
     }
 }
 
-fn manage_blocks(mut clients: Query<(&mut Client, &Position, &mut GameState, &mut ChunkLayer)>) {
-    for (client, pos, mut state, mut layer) in clients.iter_mut() {
+fn manage_blocks(
+    mut clients: Query<(
+        Entity,
+        &mut Client,
+        &Position,
+        &mut GameState,
+        &mut ChunkLayer,
+    )>,
+    entity_ids: Query<Ref<EntityId>>,
+    mut commands: Commands,
+) {
+    for (entity, mut client, pos, mut state, mut layer) in clients.iter_mut() {
+        state.tick += 1;
+        let tick = state.tick;
+        let combo = state.combo;
+        for generation in &mut state.generations {
+            generation.update(&mut layer, tick, combo, *pos);
+        }
+
+        // Move the player along with the platform they are standing on. The
+        // velocity is in blocks per tick, but the client wants it in blocks per
+        // second.
+        let mut carry = DVec3::ZERO;
+        for generation in &mut state.generations {
+            carry +=
+                generation.move_platforms(&mut commands, &mut *client, &entity_ids, tick, *pos);
+        }
+        if carry != DVec3::ZERO {
+            client.set_velocity((carry * 20.).as_vec3());
+        }
+
+        // The server still sees the player on the launch pad for a few ticks
+        // after they have been launched, so they are only launched again once
+        // they have left it.
+        let launch_velocity = state
+            .generations
+            .iter()
+            .find_map(|generation| generation.get_launch_velocity(*pos));
+        match launch_velocity {
+            Some(velocity) if !state.launched => {
+                client.set_velocity((velocity * 20.).as_vec3());
+                state.launched = true;
+            }
+            Some(_) => {}
+            None => state.launched = false,
+        }
+
         if let Some(index) = state
             .generations
             .iter()
@@ -267,9 +657,11 @@ fn manage_blocks(mut clients: Query<(&mut Client, &Position, &mut GameState, &mu
                     let state = &mut *state;
 
                     for _ in 0..index {
-                        remove_block(state, &mut layer);
-                        generate_next_block(state, &mut layer);
+                        remove_block(state, &mut layer, &mut commands);
+                        generate_next_block(state, &mut layer, &mut commands, entity);
                     }
+
+                    state.generations[0].reached_tick = Some(tick);
                 }
                 reached_thing(state, score, client, pos);
             } else {
@@ -325,12 +717,17 @@ fn manage_chunks(mut clients: Query<(&Position, &OldPosition, &mut ChunkLayer),
     }
 }
 
-fn remove_block(state: &mut GameState, world: &mut ChunkLayer) {
+fn remove_block(state: &mut GameState, world: &mut ChunkLayer, commands: &mut Commands) {
     let removed_block = state.generations.pop_front().unwrap();
-    removed_block.remove(world);
+    removed_block.remove(world, commands);
 }
 
-fn generate_next_block(state: &mut GameState, layer: &mut ChunkLayer) {
+fn generate_next_block(
+    state: &mut GameState,
+    layer: &mut ChunkLayer,
+    commands: &mut Commands,
+    entity_layer: Entity,
+) {
     let prev_gen = state.generations.back().unwrap();
 
     if prev_gen.end_state.get_block_pos().y < MIN_Y {
@@ -355,9 +752,9 @@ fn generate_next_block(state: &mut GameState, layer: &mut ChunkLayer) {
         }
     }
 
-    let next_gen = Generator::next_in_generation(state.direction, &state.theme, prev_gen);
+    let mut next_gen = Generator::next_in_generation(state.direction, &state.theme, prev_gen);
 
-    next_gen.place(layer);
+    next_gen.place(layer, commands, entity_layer, state.tick);
     state.generations.push_back(next_gen);
 
     // Combo System
@@ -0,0 +1,101 @@
+use valence::prelude::{BlockKind, BlockState, DVec3, PropName, PropValue};
+
+/// The `BlockPhysics` struct represents how a block changes the movement of a
+/// player. See the `Block` constructor calls in
+/// net.minecraft.world.level.block.Blocks for the values.
+///
+/// Properties:
+///
+/// * `friction`: The `friction` property is of type `f32`. It represents how
+/// slippery the block is when standing on it. The closer it is to 1, the longer
+/// the player keeps sliding.
+/// * `speed_factor`: The `speed_factor` property is of type `f64`. It represents
+/// what the horizontal velocity is multiplied by every tick when standing on the
+/// block.
+/// * `jump_factor`: The `jump_factor` property is of type `f64`. It represents
+/// what the jump velocity is multiplied by when jumping off of the block.
+/// * `stuck_multiplier`: The `stuck_multiplier` property is of type
+/// `Option<DVec3>`. If it is set, the player gets stuck when inside of the block,
+/// and their movement is multiplied by it.
+#[derive(Clone, Copy, Debug)]
+pub struct BlockPhysics {
+    pub friction: f32,
+    pub speed_factor: f64,
+    pub jump_factor: f64,
+    pub stuck_multiplier: Option<DVec3>,
+}
+
+impl BlockPhysics {
+    pub const DEFAULT: Self = Self {
+        friction: 0.6,
+        speed_factor: 1.0,
+        jump_factor: 1.0,
+        stuck_multiplier: None,
+    };
+
+    /// Gets the physics of the given block.
+    pub fn get(block: BlockState) -> Self {
+        match block.to_kind() {
+            BlockKind::Ice | BlockKind::PackedIce | BlockKind::FrostedIce => Self {
+                friction: 0.98,
+                ..Self::DEFAULT
+            },
+            BlockKind::BlueIce => Self {
+                friction: 0.989,
+                ..Self::DEFAULT
+            },
+            BlockKind::SlimeBlock => Self {
+                friction: 0.8,
+                ..Self::DEFAULT
+            },
+            BlockKind::SoulSand => Self {
+                speed_factor: 0.4,
+                ..Self::DEFAULT
+            },
+            BlockKind::HoneyBlock => Self {
+                speed_factor: 0.4,
+                jump_factor: 0.5,
+                ..Self::DEFAULT
+            },
+            BlockKind::Cobweb => Self {
+                stuck_multiplier: Some(DVec3::new(0.25, 0.05, 0.25)),
+                ..Self::DEFAULT
+            },
+            BlockKind::SweetBerryBush => Self {
+                stuck_multiplier: Some(DVec3::new(0.8, 0.75, 0.8)),
+                ..Self::DEFAULT
+            },
+            _ => Self::DEFAULT,
+        }
+    }
+}
+
+/// The `Fluid` enum represents a fluid the player can be in.
+///
+/// Variants:
+/// * `Water`: The `Water` variant represents still water.
+/// * `BubbleColumn`: The `BubbleColumn` variant represents a bubble column. If
+/// `drag` is true, it pulls the player down, otherwise it pushes them up.
+///
+/// `surface` is true if the block above the fluid is not the same fluid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Fluid {
+    Water { surface: bool },
+    BubbleColumn { drag: bool, surface: bool },
+}
+
+impl Fluid {
+    /// Gets the fluid of the given block, if it is one.
+    pub fn get(block: BlockState, above: BlockState) -> Option<Self> {
+        let surface = block.to_kind() != above.to_kind();
+
+        match block.to_kind() {
+            BlockKind::Water => Some(Self::Water { surface }),
+            BlockKind::BubbleColumn => Some(Self::BubbleColumn {
+                drag: block.get(PropName::Drag) == Some(PropValue::True),
+                surface,
+            }),
+            _ => None,
+        }
+    }
+}
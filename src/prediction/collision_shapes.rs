@@ -0,0 +1,239 @@
+use valence::prelude::{BlockKind, BlockState, DVec3, PropName, PropValue};
+use valence::BlockPos;
+
+use crate::utils::PLAYER_WIDTH;
+
+/// How far the player's feet can be from the top of a block while still standing
+/// on it.
+const STANDING_EPSILON: f64 = 0.001;
+
+/// The `CollisionBox` struct represents an axis aligned box that a player can
+/// collide with. Its coordinates are relative to the block it belongs to, and go
+/// from 0 to 1 for a full block.
+///
+/// Properties:
+///
+/// * `min`: The `min` property is of type `DVec3`. It represents the lowest corner
+/// of the box.
+/// * `max`: The `max` property is of type `DVec3`. It represents the highest
+/// corner of the box.
+#[derive(Clone, Copy, Debug)]
+pub struct CollisionBox {
+    pub min: DVec3,
+    pub max: DVec3,
+}
+
+impl CollisionBox {
+    pub const FULL: Self = Self {
+        min: DVec3::ZERO,
+        max: DVec3::ONE,
+    };
+
+    /// Creates a box from coordinates in sixteenths of a block, the same way the
+    /// shapes are written in the game.
+    pub fn new(x0: f64, y0: f64, z0: f64, x1: f64, y1: f64, z1: f64) -> Self {
+        Self {
+            min: DVec3::new(x0 / 16., y0 / 16., z0 / 16.),
+            max: DVec3::new(x1 / 16., y1 / 16., z1 / 16.),
+        }
+    }
+}
+
+/// Gets the collision shape of the given block. Blocks that aren't listed here
+/// are treated as full blocks, unless the player can walk through them.
+pub fn get_collision_shape(block: BlockState) -> Vec<CollisionBox> {
+    match block.to_kind() {
+        BlockKind::Air
+        | BlockKind::CaveAir
+        | BlockKind::VoidAir
+        | BlockKind::Water
+        | BlockKind::Lava
+        | BlockKind::BubbleColumn
+        | BlockKind::Cobweb
+        | BlockKind::Vine
+        | BlockKind::SweetBerryBush => vec![],
+        BlockKind::SoulSand | BlockKind::Mud => vec![CollisionBox::new(0., 0., 0., 16., 14., 16.)],
+        BlockKind::HoneyBlock => vec![CollisionBox::new(1., 0., 1., 15., 15., 15.)],
+        BlockKind::DirtPath | BlockKind::Farmland => {
+            vec![CollisionBox::new(0., 0., 0., 16., 15., 16.)]
+        }
+        _ if is_slab(block) => match block.get(PropName::Type) {
+            Some(PropValue::Bottom) => vec![CollisionBox::new(0., 0., 0., 16., 8., 16.)],
+            Some(PropValue::Top) => vec![CollisionBox::new(0., 8., 0., 16., 16., 16.)],
+            _ => vec![CollisionBox::FULL],
+        },
+        _ if is_stairs(block) => get_stairs_shape(block),
+        _ if is_trapdoor(block) => get_trapdoor_shape(block),
+        _ if is_fence(block) => {
+            get_connected_shape(block, 2., 2., 24., |value| value == PropValue::True)
+        }
+        _ if is_wall(block) => get_connected_shape(block, 4., 3., 24., |value| {
+            value == PropValue::Low || value == PropValue::Tall
+        }),
+        _ if is_pane(block) => {
+            get_connected_shape(block, 1., 1., 16., |value| value == PropValue::True)
+        }
+        _ if is_head(block) => vec![CollisionBox::new(4., 0., 4., 12., 8., 12.)],
+        _ => vec![CollisionBox::FULL],
+    }
+}
+
+/// Gets the height of the top of the given block, relative to its position.
+pub fn get_block_top(block: BlockState) -> f64 {
+    get_collision_shape(block)
+        .iter()
+        .map(|collision_box| collision_box.max.y)
+        .fold(0., f64::max)
+}
+
+/// Returns true if the player with their feet at `pos` is standing on top of one
+/// of the boxes of `shape`, placed at `block_pos`.
+pub fn is_standing_on(pos: DVec3, block_pos: BlockPos, shape: &[CollisionBox]) -> bool {
+    let half_width = PLAYER_WIDTH / 2.;
+    let block_pos = DVec3::new(block_pos.x as f64, block_pos.y as f64, block_pos.z as f64);
+
+    shape.iter().any(|collision_box| {
+        let min = block_pos + collision_box.min;
+        let max = block_pos + collision_box.max;
+
+        (pos.y - max.y).abs() < STANDING_EPSILON
+            && pos.x + half_width > min.x
+            && pos.x - half_width < max.x
+            && pos.z + half_width > min.z
+            && pos.z - half_width < max.z
+    })
+}
+
+/// Gets the shape of a stairs block. It is a slab with a step on top of it, or
+/// below it if the stairs are upside down. The step is made of quarters of the
+/// block, so that corner stairs can leave some of them out or add one more.
+fn get_stairs_shape(block: BlockState) -> Vec<CollisionBox> {
+    let (slab, step) = match block.get(PropName::Half) {
+        Some(PropValue::Top) => ((8., 16.), (0., 8.)),
+        _ => ((0., 8.), (8., 16.)),
+    };
+
+    // The side the step is on, and the side to the left of it, as x and z.
+    let facing: (i32, i32) = match block.get(PropName::Facing) {
+        Some(PropValue::North) => (0, -1),
+        Some(PropValue::South) => (0, 1),
+        Some(PropValue::West) => (-1, 0),
+        _ => (1, 0),
+    };
+    let left = (facing.1, -facing.0);
+    let right = (-left.0, -left.1);
+    let back = (-facing.0, -facing.1);
+
+    let quarters = match block.get(PropName::Shape) {
+        Some(PropValue::OuterLeft) => vec![(facing, left)],
+        Some(PropValue::OuterRight) => vec![(facing, right)],
+        Some(PropValue::InnerLeft) => vec![(facing, left), (facing, right), (back, left)],
+        Some(PropValue::InnerRight) => vec![(facing, left), (facing, right), (back, right)],
+        _ => vec![(facing, left), (facing, right)],
+    };
+
+    let get_range = |side: i32| if side < 0 { (0., 8.) } else { (8., 16.) };
+
+    let mut shape = vec![CollisionBox::new(0., slab.0, 0., 16., slab.1, 16.)];
+    for (a, b) in quarters {
+        let x = get_range(a.0 + b.0);
+        let z = get_range(a.1 + b.1);
+        shape.push(CollisionBox::new(x.0, step.0, z.0, x.1, step.1, z.1));
+    }
+
+    shape
+}
+
+fn get_trapdoor_shape(block: BlockState) -> Vec<CollisionBox> {
+    if block.get(PropName::Open) == Some(PropValue::True) {
+        // Open trapdoors stand up against the side they are facing away from.
+        return match block.get(PropName::Facing) {
+            Some(PropValue::North) => vec![CollisionBox::new(0., 0., 13., 16., 16., 16.)],
+            Some(PropValue::South) => vec![CollisionBox::new(0., 0., 0., 16., 16., 3.)],
+            Some(PropValue::West) => vec![CollisionBox::new(13., 0., 0., 16., 16., 16.)],
+            _ => vec![CollisionBox::new(0., 0., 0., 3., 16., 16.)],
+        };
+    }
+
+    match block.get(PropName::Half) {
+        Some(PropValue::Top) => vec![CollisionBox::new(0., 13., 0., 16., 16., 16.)],
+        _ => vec![CollisionBox::new(0., 0., 0., 16., 3., 16.)],
+    }
+}
+
+/// Gets the shape of a block with a post in the middle and arms going out to the
+/// sides it is connected to, like fences, walls and panes. The widths are how far
+/// the post and the arms go out from the middle of the block. Walls that have
+/// nothing on top of them can leave out the post.
+fn get_connected_shape(
+    block: BlockState,
+    post_width: f64,
+    arm_width: f64,
+    height: f64,
+    is_connected: impl Fn(PropValue) -> bool,
+) -> Vec<CollisionBox> {
+    let mut shape = Vec::new();
+
+    if block.get(PropName::Up) != Some(PropValue::False) {
+        let (min, max) = (8. - post_width, 8. + post_width);
+        shape.push(CollisionBox::new(min, 0., min, max, height, max));
+    }
+
+    let (min, max) = (8. - arm_width, 8. + arm_width);
+    let arms = [
+        (
+            PropName::North,
+            CollisionBox::new(min, 0., 0., max, height, max),
+        ),
+        (
+            PropName::East,
+            CollisionBox::new(min, 0., min, 16., height, max),
+        ),
+        (
+            PropName::South,
+            CollisionBox::new(min, 0., min, max, height, 16.),
+        ),
+        (
+            PropName::West,
+            CollisionBox::new(0., 0., min, max, height, max),
+        ),
+    ];
+
+    for (name, arm) in arms {
+        if block.get(name).map_or(false, &is_connected) {
+            shape.push(arm);
+        }
+    }
+
+    shape
+}
+
+fn is_slab(block: BlockState) -> bool {
+    block.to_kind().to_str().ends_with("_slab")
+}
+
+fn is_stairs(block: BlockState) -> bool {
+    block.to_kind().to_str().ends_with("_stairs")
+}
+
+fn is_trapdoor(block: BlockState) -> bool {
+    block.to_kind().to_str().ends_with("_trapdoor")
+}
+
+fn is_fence(block: BlockState) -> bool {
+    block.to_kind().to_str().ends_with("_fence")
+}
+
+fn is_wall(block: BlockState) -> bool {
+    block.to_kind().to_str().ends_with("_wall")
+}
+
+fn is_pane(block: BlockState) -> bool {
+    let name = block.to_kind().to_str();
+    name.ends_with("_pane") || name == "iron_bars"
+}
+
+fn is_head(block: BlockState) -> bool {
+    let name = block.to_kind().to_str();
+    (name.ends_with("_head") || name.ends_with("_skull")) && !name.contains("wall")
+}
@@ -1 +1,4 @@
+pub mod block_physics;
+pub mod collision_shapes;
 pub mod prediction_state;
+pub mod simulator;
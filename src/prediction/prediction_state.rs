@@ -2,13 +2,18 @@ use std::collections::HashSet;
 
 use rand::Rng;
 use valence::{
-    prelude::{Client, DVec3, Vec3},
+    prelude::{BlockState, Client, DVec3, Vec3},
     protocol::Particle,
     BlockPos,
 };
 
 use crate::utils::*;
 
+use super::{
+    block_physics::{BlockPhysics, Fluid},
+    collision_shapes::get_block_top,
+};
+
 /*
  * Jump: net.minecraft.world.entity.LivingEntity: line ~1950
  *   - Jump Velocity: 0.42 * BlockJumpFactor + JumpBoostPower
@@ -23,10 +28,29 @@ use crate::utils::*;
  *   Block friction is usually 0.6
  *   - If sprinting, Horizontal Velocity += 0.2 (relative to direction)
  *   - If sneaking, Horizontal Velocity *= 0.3
+ *
+ * Block speed factor: net.minecraft.world.entity.Entity: move
+ *   - After moving, Horizontal Velocity *= BlockSpeedFactor (soul sand, honey)
+ *   - If stuck in a block (cobweb), the movement is multiplied by the block's
+ *     multiplier and the velocity is reset to 0
+ *
+ * Climbing: net.minecraft.world.entity.LivingEntity: handleOnClimbable
+ *   - Horizontal Velocity is clamped to 0.15, Vertical Velocity to at least -0.15
+ *   - If pushing into the climbable block, Vertical Velocity = 0.2 after moving
+ *
+ * Water: net.minecraft.world.entity.LivingEntity: travel
+ *   - Speed is 0.02, Velocity *= 0.8 after moving, Vertical Velocity -= 0.005
+ *   - Holding jump adds 0.04 to Vertical Velocity
+ *   - Pushing into a block at the surface sets Vertical Velocity to 0.3
+ *
+ * Bubble columns: net.minecraft.world.entity.Entity: onInsideBubbleColumn
+ *   - Up: Vertical Velocity += 0.06 (max 0.7), at the surface += 0.1 (max 1.8)
+ *   - Down: Vertical Velocity -= 0.03 (min -0.3), at the surface min -0.9
+ *
+ * Slime: net.minecraft.world.level.block.SlimeBlock (updateEntityAfterFallOn)
+ *   - If not sneaking and falling, Vertical Velocity *= -1 (-0.8 for non-living entities)
  */
 const FRICTION: f32 = 0.91;
-const BLOCK_FRICTION: f32 = 0.6;
-const ON_GROUND: bool = false;
 const SPEED: f32 = 0.13000001;
 const FLYING_SPEED: f32 = 0.02;
 
@@ -34,6 +58,18 @@ const AVG_RUNNING_SPEED: f64 = 0.28;
 const AVG_RUN_JUMP_SPEED: f64 = 0.47;
 const JUMP_VELOCITY: f64 = 0.42;
 const JUMP_HEAD_HIT: f64 = 0.2;
+const SPRINT_JUMP_BOOST: f64 = 0.2;
+const CLIMB_SPEED: f64 = 0.2;
+const CLIMB_MIN_VELOCITY: f64 = -0.15;
+const WATER_SPEED: f32 = 0.02;
+const WATER_DRAG: f64 = 0.8;
+const WATER_GRAVITY: f64 = 0.005;
+const WATER_SWIM_UP: f64 = 0.04;
+const WATER_EXIT_VELOCITY: f64 = 0.3;
+
+/// How many blocks above the floor the ceiling of a head hit jump is. The player
+/// is 1.8 blocks tall, so they hit their head after rising 0.2 blocks.
+pub const HEAD_HIT_CEILING: i32 = 3;
 
 // const PLAYER_WIDTH: f64 = 0.6;
 // const PLAYER_HEIGHT: f64 = 1.8;
@@ -47,6 +83,13 @@ pub struct PredictionState {
     pub vel: DVec3,
     pub yaw: f32, // pitch doesn't matter for movement
     pub color: Vec3,
+    pub bounce_y: Option<f64>, // the top of a slime block to bounce on when falling through it
+    pub ceiling: Option<BlockPos>, // a block the player hits their head on when jumping under it
+    pub climb_top: Option<f64>, // the y the player is climbing up to
+    pub on_ground: bool,
+    pub ground: BlockPhysics, // the block the player is standing on
+    pub stuck_multiplier: Option<DVec3>, // set if the player is inside of a block like a cobweb
+    pub fluid: Option<Fluid>, // the fluid the player is in
 }
 
 /// A player's state at a given point in time.
@@ -62,6 +105,13 @@ impl PredictionState {
                 rand::thread_rng().gen_range(0f32..1f32),
                 rand::thread_rng().gen_range(0f32..1f32),
             ),
+            bounce_y: None,
+            ceiling: None,
+            climb_top: None,
+            on_ground: false,
+            ground: BlockPhysics::DEFAULT,
+            stuck_multiplier: None,
+            fluid: None,
         }
     }
 
@@ -70,6 +120,14 @@ impl PredictionState {
         Self::running_jump_vec(get_edge_of_block(block_pos, yaw), yaw)
     }
 
+    /// Like `running_jump_block`, but for blocks that may not be a full block
+    /// tall, like slabs and fences.
+    pub fn running_jump_shape(block_pos: BlockPos, block: BlockState, yaw: f32) -> Self {
+        let mut pos = get_edge_of_block(block_pos, yaw);
+        pos.y += get_block_top(block);
+        Self::running_jump_vec(pos, yaw)
+    }
+
     pub fn running_jump_vec(pos: DVec3, yaw: f32) -> Self {
         let mut state = Self::new(pos, DVec3::ZERO, yaw);
         state.vel.x = -AVG_RUN_JUMP_SPEED * yaw.sin() as f64;
@@ -86,6 +144,100 @@ impl PredictionState {
         state
     }
 
+    /// A running jump off of `block_pos` with a ceiling `HEAD_HIT_CEILING` blocks
+    /// above it. The player hits their head right after jumping, which makes the
+    /// jump shorter but faster.
+    pub fn running_jump_ceiling(block_pos: BlockPos, yaw: f32) -> Self {
+        let mut state = Self::running_jump_block(block_pos, yaw);
+        state.ceiling = Some(BlockPos::new(
+            block_pos.x,
+            block_pos.y + HEAD_HIT_CEILING,
+            block_pos.z,
+        ));
+        state
+    }
+
+    /// The player standing on `block_pos`, pushing into a ladder or vine that
+    /// goes up until `top_y`. Ticking the state climbs it.
+    pub fn climb(block_pos: BlockPos, top_y: f64, yaw: f32) -> Self {
+        let pos = BlockPos::new(block_pos.x, block_pos.y + 1, block_pos.z);
+        let mut state = Self::new(
+            get_edge_of_block_dist(pos, yaw, 0.5 - crate::utils::PLAYER_WIDTH / 2.),
+            DVec3::ZERO,
+            yaw,
+        );
+        state.climb_top = Some(top_y);
+        state
+    }
+
+    /// The player standing still in the middle of `block_pos`.
+    pub fn standing(block_pos: BlockPos, block: BlockState, yaw: f32) -> Self {
+        let pos = BlockPos::new(block_pos.x, block_pos.y + 1, block_pos.z);
+        let mut state = Self::new(get_edge_of_block(pos, yaw), DVec3::ZERO, yaw);
+        state.on_ground = true;
+        state.ground = BlockPhysics::get(block);
+        state
+    }
+
+    /// Makes the player sprint jump off of the block they are standing on.
+    pub fn jump(&mut self) {
+        self.vel.y = JUMP_VELOCITY * self.ground.jump_factor;
+        self.vel.x += -SPRINT_JUMP_BOOST * self.yaw.sin() as f64;
+        self.vel.z += SPRINT_JUMP_BOOST * self.yaw.cos() as f64;
+        self.on_ground = false;
+    }
+
+    /// Turns the player towards `yaw`. Their horizontal velocity is turned along
+    /// with them, so they keep their speed.
+    pub fn turn(&mut self, yaw: f32) {
+        let (sin, cos) = (yaw - self.yaw).sin_cos();
+        let (sin, cos) = (sin as f64, cos as f64);
+
+        self.vel = DVec3::new(
+            self.vel.x * cos - self.vel.z * sin,
+            self.vel.y,
+            self.vel.z * cos + self.vel.x * sin,
+        );
+        self.yaw = yaw;
+    }
+
+    /// Ticks the state until it is falling and at or below `target_y`. The state
+    /// is left at the last tick before that happens.
+    ///
+    /// Returns the number of ticks that were simulated.
+    pub fn tick_until_landed(&mut self, target_y: f64) -> usize {
+        let mut ticks = 0;
+
+        loop {
+            let mut new_state = *self;
+            new_state.tick();
+
+            if new_state.vel.y > 0. || new_state.pos.y > target_y {
+                *self = new_state;
+                ticks += 1;
+            } else {
+                return ticks;
+            }
+        }
+    }
+
+    /// Gets every state from this one up to the one `tick_until_landed` would stop
+    /// at, including both.
+    pub fn get_states_until_landed(&self, target_y: f64) -> Vec<Self> {
+        let mut state = *self;
+        let ticks = state.tick_until_landed(target_y);
+
+        let mut states = Vec::with_capacity(ticks + 1);
+        state = *self;
+        states.push(state);
+        for _ in 0..ticks {
+            state.tick();
+            states.push(state);
+        }
+
+        states
+    }
+
     /// Gets the block pos below the player's feet.
     pub fn get_block_pos(&self) -> BlockPos {
         BlockPos::new(
@@ -119,17 +271,79 @@ impl PredictionState {
     }
 
     pub fn tick(&mut self) {
+        if let Some(fluid) = self.fluid {
+            self.tick_fluid(fluid);
+            return;
+        }
+
         let mut vel = self.handle_relative_friction_and_calculate_movement(self.get_accel());
 
-        vel.y -= 0.08; // gravity
-        vel.y *= 0.9800000190734863; // drag
+        let friction = if self.on_ground {
+            vel.y = 0.;
+            self.ground.friction * FRICTION
+        } else {
+            vel.y -= 0.08; // gravity
+            vel.y *= 0.9800000190734863; // drag
+            FRICTION
+        };
 
-        vel.x *= FRICTION as f64;
-        vel.z *= FRICTION as f64;
+        vel.x *= friction as f64;
+        vel.z *= friction as f64;
 
         self.vel = vel;
     }
 
+    /// Ticks the state with the player looking towards `yaw`. Changing the yaw
+    /// mid-air changes which way the player accelerates, which lets them curve
+    /// their jump.
+    pub fn tick_yaw(&mut self, yaw: f32) {
+        self.yaw = yaw;
+        self.tick();
+    }
+
+    /// Ticks the state while the player is in a fluid. The player holds jump,
+    /// unless they are being pulled down, and only swims forward once they are
+    /// at the surface.
+    fn tick_fluid(&mut self, fluid: Fluid) {
+        let (drag, surface) = match fluid {
+            Fluid::Water { surface } => (false, surface),
+            Fluid::BubbleColumn { drag, surface } => (drag, surface),
+        };
+
+        if !drag {
+            self.vel.y += WATER_SWIM_UP;
+        }
+        if surface {
+            self.move_relative(WATER_SPEED, self.get_accel());
+        }
+
+        self.pos += self.vel;
+
+        self.vel *= WATER_DRAG;
+        self.vel.y -= WATER_GRAVITY;
+
+        self.vel.y = match fluid {
+            Fluid::Water { surface: true } => self.vel.y.max(WATER_EXIT_VELOCITY),
+            Fluid::Water { surface: false } => self.vel.y,
+            Fluid::BubbleColumn {
+                drag: false,
+                surface: true,
+            } => (self.vel.y + 0.1).min(1.8),
+            Fluid::BubbleColumn {
+                drag: false,
+                surface: false,
+            } => (self.vel.y + 0.06).min(0.7),
+            Fluid::BubbleColumn {
+                drag: true,
+                surface: true,
+            } => (self.vel.y - 0.03).max(-0.9),
+            Fluid::BubbleColumn {
+                drag: true,
+                surface: false,
+            } => (self.vel.y - 0.03).max(-0.3),
+        };
+    }
+
     fn draw_particle(&self, client: &mut Client) {
         client.play_particle(
             &Particle::Dust {
@@ -164,18 +378,94 @@ impl PredictionState {
     }
 
     fn handle_relative_friction_and_calculate_movement(&mut self, accel: DVec3) -> DVec3 {
-        self.move_relative(self.get_friction_influenced_speed(BLOCK_FRICTION), accel);
-        self.pos += self.vel;
+        self.move_relative(
+            self.get_friction_influenced_speed(self.ground.friction),
+            accel,
+        );
+        self.handle_climb_velocity();
+        self.handle_move();
+        self.handle_ceiling();
+        self.handle_bounce();
+        self.handle_climb();
         self.vel
     }
 
+    /// Limits the velocity while climbing. The player is pushing into the wall
+    /// behind the ladder, so they don't move horizontally.
+    fn handle_climb_velocity(&mut self) {
+        if self.climb_top.is_some() {
+            self.vel.x = 0.;
+            self.vel.z = 0.;
+            self.vel.y = self.vel.y.max(CLIMB_MIN_VELOCITY);
+        }
+    }
+
+    /// Keeps the player climbing until their feet are above `climb_top`.
+    fn handle_climb(&mut self) {
+        if let Some(climb_top) = self.climb_top {
+            if self.pos.y < climb_top {
+                self.vel.y = CLIMB_SPEED;
+            } else {
+                self.climb_top = None;
+            }
+        }
+    }
+
+    /// Moves the player by their velocity, taking the block they are stuck in
+    /// and the block they are standing on into account.
+    fn handle_move(&mut self) {
+        if let Some(stuck_multiplier) = self.stuck_multiplier {
+            self.pos += self.vel * stuck_multiplier;
+            self.vel = DVec3::ZERO;
+        } else {
+            self.pos += self.vel;
+        }
+
+        if self.on_ground {
+            self.vel.x *= self.ground.speed_factor;
+            self.vel.z *= self.ground.speed_factor;
+        }
+    }
+
+    /// Stops the player from moving up through `ceiling` if they are under it.
+    fn handle_ceiling(&mut self) {
+        if let Some(ceiling) = self.ceiling {
+            // The real player size is used here, since the ceiling is always hit
+            // if the player is under it at all.
+            let half_width = crate::utils::PLAYER_WIDTH / 2.;
+            let height = crate::utils::PLAYER_HEIGHT;
+
+            let is_under = self.pos.x + half_width > ceiling.x as f64
+                && self.pos.x - half_width < ceiling.x as f64 + 1.
+                && self.pos.z + half_width > ceiling.z as f64
+                && self.pos.z - half_width < ceiling.z as f64 + 1.;
+
+            if is_under && self.vel.y > 0. && self.pos.y + height > ceiling.y as f64 {
+                self.pos.y = ceiling.y as f64 - height;
+                self.vel.y = 0.;
+            }
+        }
+    }
+
+    /// Bounces the player back up if they fell through `bounce_y`. The player
+    /// only bounces once.
+    fn handle_bounce(&mut self) {
+        if let Some(bounce_y) = self.bounce_y {
+            if self.vel.y < 0. && self.pos.y <= bounce_y {
+                self.pos.y = bounce_y;
+                self.vel.y = -self.vel.y;
+                self.bounce_y = None;
+            }
+        }
+    }
+
     fn move_relative(&mut self, speed: f32, accel: DVec3) {
         let vec3 = get_input_vector(accel, speed, self.yaw);
         self.vel += vec3;
     }
 
     fn get_friction_influenced_speed(&self, f: f32) -> f32 {
-        if ON_GROUND {
+        if self.on_ground {
             SPEED * (0.21600002f32 / (f * f * f))
         } else {
             FLYING_SPEED
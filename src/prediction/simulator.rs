@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+
+use valence::{
+    prelude::{BlockState, ChunkLayer, DVec3},
+    BlockPos,
+};
+
+use crate::utils::*;
+
+use super::{
+    block_physics::BlockPhysics,
+    collision_shapes::{get_collision_shape, CollisionBox},
+    prediction_state::PredictionState,
+};
+
+/*
+ * Collision: net.minecraft.world.entity.Entity: collide
+ *   - The movement is resolved one axis at a time: first Y, then whichever of X
+ *     and Z is the largest, then the other one.
+ *   - For every axis, the movement is cut short at the first box in the way.
+ *     Boxes the player is already inside of are ignored.
+ *   - If the movement on an axis is cut short, the velocity on that axis is 0.
+ *   - The player is on the ground if their downwards movement was cut short.
+ *
+ * Block below: net.minecraft.world.entity.Entity: getBlockPosBelowThatAffectsMyMovement
+ *   - The block 0.5000001 below the player's feet decides the friction.
+ */
+
+/// How close two positions have to be to count as the same.
+const COLLISION_EPSILON: f64 = 1e-7;
+
+/// How far below their feet the player has to be standing on something to count
+/// as on the ground, when they didn't move down this tick.
+const GROUND_PROBE: f64 = 0.001;
+
+/// How far the landing can be from the expected height.
+const LANDING_EPSILON: f64 = 0.001;
+
+/// How many ticks a jump can take before giving up on it.
+const MAX_JUMP_TICKS: usize = 200;
+
+/// The `BlockLookup` trait is implemented by anything the simulator can get
+/// blocks from. Positions that don't have a block are air.
+pub trait BlockLookup {
+    fn get_block(&self, pos: BlockPos) -> BlockState;
+}
+
+impl BlockLookup for HashMap<BlockPos, BlockState> {
+    fn get_block(&self, pos: BlockPos) -> BlockState {
+        self.get(&pos).copied().unwrap_or(BlockState::AIR)
+    }
+}
+
+impl BlockLookup for ChunkLayer {
+    fn get_block(&self, pos: BlockPos) -> BlockState {
+        self.block(pos).map_or(BlockState::AIR, |block| block.state)
+    }
+}
+
+/// The `Collisions` struct represents what the player bumped into during a tick.
+///
+/// Properties:
+///
+/// * `on_ground`: The `on_ground` property is of type `bool`. It represents
+/// whether or not the player is standing on something after the tick.
+/// * `hit_wall`: The `hit_wall` property is of type `bool`. It represents whether
+/// or not the horizontal movement of the player was cut short.
+/// * `hit_ceiling`: The `hit_ceiling` property is of type `bool`. It represents
+/// whether or not the player bonked their head.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Collisions {
+    pub on_ground: bool,
+    pub hit_wall: bool,
+    pub hit_ceiling: bool,
+}
+
+/// Simulates a single tick of movement like `PredictionState::tick`, but the
+/// player collides with the blocks in `lookup`.
+pub fn simulate_tick(state: &mut PredictionState, lookup: &impl BlockLookup) -> Collisions {
+    let start = state.pos;
+    state.tick();
+    let movement = state.pos - start;
+
+    let mut player = get_player_box(start);
+    let boxes = get_nearby_boxes(&player, movement, lookup);
+
+    let axes = if movement.x.abs() < movement.z.abs() {
+        [1, 2, 0]
+    } else {
+        [1, 0, 2]
+    };
+
+    let mut resolved = DVec3::ZERO;
+    for axis in axes {
+        resolved[axis] = collide_axis(&player, &boxes, axis, movement[axis]);
+        player.min[axis] += resolved[axis];
+        player.max[axis] += resolved[axis];
+    }
+
+    state.pos = start + resolved;
+
+    let hit = |axis: usize| (resolved[axis] - movement[axis]).abs() > COLLISION_EPSILON;
+
+    if hit(0) {
+        state.vel.x = 0.;
+    }
+    if hit(1) {
+        state.vel.y = 0.;
+    }
+    if hit(2) {
+        state.vel.z = 0.;
+    }
+
+    let on_ground = (hit(1) && movement.y < 0.)
+        || collide_axis(&player, &boxes, 1, -GROUND_PROBE) > -GROUND_PROBE;
+
+    state.on_ground = on_ground;
+    if on_ground {
+        let below = BlockPos::new(
+            state.pos.x.floor() as i32,
+            (state.pos.y - 0.5000001).floor() as i32,
+            state.pos.z.floor() as i32,
+        );
+        state.ground = BlockPhysics::get(lookup.get_block(below));
+    }
+
+    Collisions {
+        on_ground,
+        hit_wall: hit(0) || hit(2),
+        hit_ceiling: hit(1) && movement.y > 0.,
+    }
+}
+
+/// The `JumpOutcome` enum represents how a simulated jump ended.
+///
+/// Variants:
+///
+/// * `Landed`: The `Landed` variant means the player landed at the expected
+/// height without bumping into anything.
+/// * `HitWall`: The `HitWall` variant means the player ran into the side of a
+/// block at the given position.
+/// * `HitCeiling`: The `HitCeiling` variant means the player hit their head at the
+/// given position.
+/// * `LandedElsewhere`: The `LandedElsewhere` variant means the player landed at
+/// a different height, with their feet at the given position.
+/// * `Missed`: The `Missed` variant means the player fell past the expected height
+/// without landing on anything.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JumpOutcome {
+    Landed,
+    HitWall(DVec3),
+    HitCeiling(DVec3),
+    LandedElsewhere(DVec3),
+    Missed,
+}
+
+/// Simulates a jump until the player lands, bumps into something, or falls past
+/// `target_y`, which is the height their feet are expected to land at.
+pub fn simulate_jump(
+    mut state: PredictionState,
+    target_y: f64,
+    lookup: &impl BlockLookup,
+) -> JumpOutcome {
+    for _ in 0..MAX_JUMP_TICKS {
+        let collisions = simulate_tick(&mut state, lookup);
+
+        if collisions.hit_wall {
+            return JumpOutcome::HitWall(state.pos);
+        }
+
+        if collisions.hit_ceiling {
+            return JumpOutcome::HitCeiling(state.pos);
+        }
+
+        if collisions.on_ground {
+            if (state.pos.y - target_y).abs() < LANDING_EPSILON {
+                return JumpOutcome::Landed;
+            }
+
+            return JumpOutcome::LandedElsewhere(state.pos);
+        }
+
+        if state.pos.y < target_y && state.vel.y < 0. {
+            return JumpOutcome::Missed;
+        }
+    }
+
+    JumpOutcome::Missed
+}
+
+/// Returns true if the player lands with their feet at `target_y`, without
+/// hitting a wall or a ceiling on the way.
+pub fn is_jump_clear(state: PredictionState, target_y: f64, lookup: &impl BlockLookup) -> bool {
+    simulate_jump(state, target_y, lookup) == JumpOutcome::Landed
+}
+
+/// Gets the hitbox of the player with their feet at `pos`.
+fn get_player_box(pos: DVec3) -> CollisionBox {
+    CollisionBox {
+        min: pos - DVec3::new(PLAYER_WIDTH / 2., 0., PLAYER_WIDTH / 2.),
+        max: pos + DVec3::new(PLAYER_WIDTH / 2., PLAYER_HEIGHT, PLAYER_WIDTH / 2.),
+    }
+}
+
+/// Gets the boxes of every block the player could bump into while moving by
+/// `movement`, relative to the world instead of their block. Blocks below are
+/// included too, since some blocks, like fences, are taller than a full block.
+fn get_nearby_boxes(
+    player: &CollisionBox,
+    movement: DVec3,
+    lookup: &impl BlockLookup,
+) -> Vec<CollisionBox> {
+    let min = player.min.min(player.min + movement) - DVec3::splat(GROUND_PROBE);
+    let max = player.max.max(player.max + movement);
+
+    let mut boxes = Vec::new();
+
+    for x in min.x.floor() as i32..=max.x.floor() as i32 {
+        for y in min.y.floor() as i32 - 1..=max.y.floor() as i32 {
+            for z in min.z.floor() as i32..=max.z.floor() as i32 {
+                let pos = BlockPos::new(x, y, z);
+                let offset = DVec3::new(x as f64, y as f64, z as f64);
+
+                for collision_box in get_collision_shape(lookup.get_block(pos)) {
+                    boxes.push(CollisionBox {
+                        min: collision_box.min + offset,
+                        max: collision_box.max + offset,
+                    });
+                }
+            }
+        }
+    }
+
+    boxes
+}
+
+/// Gets how far the player can move along `axis`, up to `movement`, before they
+/// bump into one of the boxes.
+fn collide_axis(player: &CollisionBox, boxes: &[CollisionBox], axis: usize, movement: f64) -> f64 {
+    let mut movement = movement;
+
+    for collision_box in boxes {
+        let overlaps = (0..3).filter(|other| *other != axis).all(|other| {
+            player.max[other] > collision_box.min[other] + COLLISION_EPSILON
+                && player.min[other] < collision_box.max[other] - COLLISION_EPSILON
+        });
+
+        if !overlaps {
+            continue;
+        }
+
+        if movement > 0. && collision_box.min[axis] >= player.max[axis] - COLLISION_EPSILON {
+            movement = movement.min(collision_box.min[axis] - player.max[axis]);
+        } else if movement < 0. && collision_box.max[axis] <= player.min[axis] + COLLISION_EPSILON {
+            movement = movement.max(collision_box.max[axis] - player.min[axis]);
+        }
+    }
+
+    movement
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Gets a world with a stone block at 0, 0, 0 to jump off of.
+    fn get_world() -> HashMap<BlockPos, BlockState> {
+        HashMap::from([(BlockPos::new(0, 0, 0), BlockState::STONE)])
+    }
+
+    /// Fills the area from `min` to `max` with `block`.
+    fn fill(
+        world: &mut HashMap<BlockPos, BlockState>,
+        block: BlockState,
+        min: BlockPos,
+        max: BlockPos,
+    ) {
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                for z in min.z..=max.z {
+                    world.insert(BlockPos::new(x, y, z), block);
+                }
+            }
+        }
+    }
+
+    /// Gets a straight running jump off of the block at 0, 0, 0, towards positive z.
+    fn get_jump() -> PredictionState {
+        PredictionState::running_jump_block(BlockPos::new(0, 0, 0), 0.)
+    }
+
+    #[test]
+    fn stays_on_ground() {
+        let mut state = PredictionState::standing(BlockPos::new(0, 0, 0), BlockState::STONE, 0.);
+
+        let collisions = simulate_tick(&mut state, &get_world());
+
+        assert!(collisions.on_ground);
+        assert!(!collisions.hit_wall);
+        assert_eq!(state.pos.y, 1.);
+    }
+
+    #[test]
+    fn lands_on_platform() {
+        let mut world = get_world();
+        fill(
+            &mut world,
+            BlockState::STONE,
+            BlockPos::new(-1, 0, 3),
+            BlockPos::new(1, 0, 7),
+        );
+
+        assert_eq!(simulate_jump(get_jump(), 1., &world), JumpOutcome::Landed);
+        assert!(is_jump_clear(get_jump(), 1., &world));
+    }
+
+    #[test]
+    fn misses_platform_out_of_reach() {
+        let mut world = get_world();
+        world.insert(BlockPos::new(0, 0, 10), BlockState::STONE);
+
+        assert_eq!(simulate_jump(get_jump(), 1., &world), JumpOutcome::Missed);
+        assert!(!is_jump_clear(get_jump(), 1., &world));
+    }
+
+    #[test]
+    fn hits_wall() {
+        let mut world = get_world();
+        fill(
+            &mut world,
+            BlockState::STONE,
+            BlockPos::new(-1, 0, 3),
+            BlockPos::new(1, 0, 7),
+        );
+        fill(
+            &mut world,
+            BlockState::STONE,
+            BlockPos::new(-2, 1, 2),
+            BlockPos::new(2, 3, 2),
+        );
+
+        assert!(matches!(
+            simulate_jump(get_jump(), 1., &world),
+            JumpOutcome::HitWall(_)
+        ));
+        assert!(!is_jump_clear(get_jump(), 1., &world));
+    }
+
+    #[test]
+    fn hits_ceiling() {
+        let mut world = get_world();
+        fill(
+            &mut world,
+            BlockState::STONE,
+            BlockPos::new(-1, 0, 3),
+            BlockPos::new(1, 0, 7),
+        );
+        fill(
+            &mut world,
+            BlockState::STONE,
+            BlockPos::new(-1, 3, -1),
+            BlockPos::new(1, 3, 1),
+        );
+
+        match simulate_jump(get_jump(), 1., &world) {
+            JumpOutcome::HitCeiling(pos) => assert!((pos.y + PLAYER_HEIGHT - 3.).abs() < 1e-6),
+            outcome => panic!("expected the jump to hit the ceiling, got {outcome:?}"),
+        }
+    }
+
+    #[test]
+    fn lands_on_slab() {
+        let mut world = get_world();
+        fill(
+            &mut world,
+            BlockState::STONE_SLAB,
+            BlockPos::new(-1, 0, 2),
+            BlockPos::new(1, 0, 7),
+        );
+
+        assert_eq!(simulate_jump(get_jump(), 0.5, &world), JumpOutcome::Landed);
+        assert!(!is_jump_clear(get_jump(), 1., &world));
+    }
+
+    #[test]
+    fn lands_on_wall() {
+        // The posts of walls are close enough together that the player can't
+        // fall between them.
+        let mut world = get_world();
+        fill(
+            &mut world,
+            BlockState::COBBLESTONE_WALL,
+            BlockPos::new(-1, 0, 2),
+            BlockPos::new(1, 0, 7),
+        );
+
+        assert_eq!(simulate_jump(get_jump(), 1.5, &world), JumpOutcome::Landed);
+        assert!(matches!(
+            simulate_jump(get_jump(), 1., &world),
+            JumpOutcome::LandedElsewhere(pos) if (pos.y - 1.5).abs() < 1e-6
+        ));
+    }
+}
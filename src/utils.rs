@@ -1,11 +1,11 @@
 #![allow(dead_code)]
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use rand::Rng;
 use valence::{
     math::IVec3,
-    prelude::{Client, DVec3, PropName, PropValue, Vec3},
+    prelude::{BlockState, Client, DVec3, PropName, PropValue, Vec3},
     protocol::Particle,
     BlockPos,
 };
@@ -196,8 +196,31 @@ pub fn get_blocks_between(start: Vec3, end: Vec3) -> Vec<BlockPos> {
     blocks
 }
 
+/// Returns true if the player's hitbox hits any of the given blocks while moving
+/// in a straight line from `from` to `to`.
+pub fn hitbox_hits_blocks_between(from: DVec3, to: DVec3, blocks: &HashSet<BlockPos>) -> bool {
+    for x in [-PLAYER_WIDTH / 2., PLAYER_WIDTH / 2.] {
+        for y in [0., PLAYER_HEIGHT / 2., PLAYER_HEIGHT] {
+            for z in [-PLAYER_WIDTH / 2., PLAYER_WIDTH / 2.] {
+                let offset = DVec3::new(x, y, z);
+
+                if get_blocks_between((from + offset).as_vec3(), (to + offset).as_vec3())
+                    .iter()
+                    .any(|pos| blocks.contains(pos))
+                {
+                    return true;
+                }
+            }
+        }
+    }
+
+    false
+}
+
 pub fn prediction_can_reach(from: DVec3, to: BlockPos) -> bool {
-    let yaw = (to.x as f64 - from.x).atan2(to.z as f64 - from.z) as f32;
+    // Aim at the center of the block. The velocity of a yaw goes towards -x, so
+    // the angle is flipped.
+    let yaw = -(to.x as f64 + 0.5 - from.x).atan2(to.z as f64 + 0.5 - from.z) as f32;
 
     let mut state = PredictionState::running_jump_vec(from, yaw);
 
@@ -410,6 +433,44 @@ pub fn prop_value_flip_x(value: PropValue) -> PropValue {
     }
 }
 
+/// The property names that can change when a block state is rotated or flipped.
+const DIRECTIONAL_PROPS: [PropName; 8] = [
+    PropName::Facing,
+    PropName::Axis,
+    PropName::Orientation,
+    PropName::Shape,
+    PropName::North,
+    PropName::East,
+    PropName::South,
+    PropName::West,
+];
+
+/// Rotates the given block state clockwise.
+pub fn rotate_block_state_cw(block: BlockState) -> BlockState {
+    let nvs = DIRECTIONAL_PROPS
+        .iter()
+        .filter_map(|name| block.get(*name).map(|value| (*name, value)))
+        .collect::<Vec<PropNameValue>>();
+
+    nvs.iter().fold(block, |block, nv| {
+        let (name, value) = prop_nv_rotate_cw(nv);
+        block.set(name, value)
+    })
+}
+
+/// Flips the given block state on the x axis.
+pub fn flip_block_state_x(block: BlockState) -> BlockState {
+    let nvs = DIRECTIONAL_PROPS
+        .iter()
+        .filter_map(|name| block.get(*name).map(|value| (*name, value)))
+        .collect::<Vec<PropNameValue>>();
+
+    nvs.iter().fold(block, |block, nv| {
+        let (name, value) = prop_nv_flip_x(nv);
+        block.set(name, value)
+    })
+}
+
 /// Rotates a `HashSet<BlockPos>` clockwise along the Y axis around a given point.
 pub fn rotate_block_set_cw(set: &HashSet<BlockPos>, origin: BlockPos) -> HashSet<BlockPos> {
     let mut blocks = HashSet::new();
@@ -422,6 +483,21 @@ pub fn rotate_block_set_cw(set: &HashSet<BlockPos>, origin: BlockPos) -> HashSet
     blocks
 }
 
+/// Rotates a `HashMap<BlockPos, BlockState>` clockwise along the Y axis around a
+/// given point. The block states are rotated as well.
+pub fn rotate_block_map_cw(
+    map: &HashMap<BlockPos, BlockState>,
+    origin: BlockPos,
+) -> HashMap<BlockPos, BlockState> {
+    let mut blocks = HashMap::new();
+
+    for (pos, block) in map {
+        blocks.insert(pos.rotate_cw(origin), rotate_block_state_cw(*block));
+    }
+
+    blocks
+}
+
 /// Flip a `HashSet<BlockPos>` along the X axis around a given point.
 pub fn flip_block_set_x(set: &HashSet<BlockPos>, origin: BlockPos) -> HashSet<BlockPos> {
     let mut blocks = HashSet::new();
@@ -434,6 +510,21 @@ pub fn flip_block_set_x(set: &HashSet<BlockPos>, origin: BlockPos) -> HashSet<Bl
     blocks
 }
 
+/// Flip a `HashMap<BlockPos, BlockState>` along the X axis around a given point.
+/// The block states are flipped as well.
+pub fn flip_block_map_x(
+    map: &HashMap<BlockPos, BlockState>,
+    origin: BlockPos,
+) -> HashMap<BlockPos, BlockState> {
+    let mut blocks = HashMap::new();
+
+    for (pos, block) in map {
+        blocks.insert(pos.flip_x(origin), flip_block_state_x(*block));
+    }
+
+    blocks
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum JumpDirection {
     Up,